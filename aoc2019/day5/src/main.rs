@@ -1,13 +1,31 @@
-mod intcode_processor;
+use aoc_helpers::input;
+use intcode::{parse_program, ComputerState, IntcodeComputer, Word};
 
-use std::fs;
+/// Run the diagnostic program for the system with ID `system_id`, returning the diagnostic code
+fn diagnose(program: &[Word], system_id: Word) -> Result<Word, String> {
+    let mut computer = IntcodeComputer::new(program.to_vec());
+    computer.input.push_back(system_id);
+    match computer.run().map_err(|e| e.to_string())? {
+        ComputerState::Halt => {}
+        other => return Err(format!("Diagnostic program stopped early: {:?}", other)),
+    }
+
+    // Every output but the last is a test result, which should all have passed
+    let code = computer
+        .output
+        .pop_back()
+        .ok_or("No diagnostic code output")?;
+    if let Some(failed) = computer.output.iter().position(|&x| x != 0) {
+        return Err(format!("Diagnostic test {} failed", failed));
+    }
+    Ok(code)
+}
 
 fn main() -> Result<(), String> {
-    let mut raw = fs::read_to_string("data.txt").unwrap();
-    // Remove newline
-    raw.pop();
-    let data: Vec<i32> = raw.split(",").map(|x| x.parse().unwrap()).collect();
-    let mut computer = intcode_processor::IntcodeComputer::new(data, std::io::stdin());
-    computer.step();
+    let raw = input::load(2019, 5).map_err(|e| e.to_string())?;
+    let program = parse_program(&raw).map_err(|e| e.to_string())?;
+
+    println!("Part 1: {}", diagnose(&program, 1)?);
+    println!("Part 2: {}", diagnose(&program, 5)?);
     Ok(())
 }
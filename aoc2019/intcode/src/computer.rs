@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+
+use crate::error::IntcodeError;
+use crate::io::{Input, Output};
+use crate::opcode::{Instruction, Mode, OpCode};
+use crate::Word;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ComputerState {
+    /// Ready to execute the next instruction
+    Continue,
+    /// Stopped at an input instruction with no input available
+    ///
+    /// Stepping again once more input has been provided will pick up where it left off.
+    NeedsInput,
+    Halt,
+    Error(IntcodeError),
+}
+
+/// An Intcode machine, reading from `I` and writing to `O`
+///
+/// By default, both are [`VecDeque`]s, so input can be pushed onto the back of
+/// [`IntcodeComputer::input`] and output popped off the front of [`IntcodeComputer::output`],
+/// which makes it easy to chain machines together.
+#[derive(Debug, Clone)]
+pub struct IntcodeComputer<I = VecDeque<Word>, O = VecDeque<Word>> {
+    /// Memory, which grows as needed whenever the program writes past the end
+    pub mem: Vec<Word>,
+    pub input: I,
+    pub output: O,
+    i_pointer: usize,
+    relative_base: Word,
+    pub state: ComputerState,
+}
+
+impl IntcodeComputer {
+    /// Get a new IntcodeComputer with the given memory state, and empty input and output queues
+    pub fn new(mem: Vec<Word>) -> IntcodeComputer {
+        IntcodeComputer::with_io(mem, VecDeque::new(), VecDeque::new())
+    }
+}
+
+impl<I: Input, O: Output> IntcodeComputer<I, O> {
+    /// Get a new IntcodeComputer with the given memory state, input and output
+    pub fn with_io(mem: Vec<Word>, input: I, output: O) -> IntcodeComputer<I, O> {
+        IntcodeComputer {
+            mem,
+            input,
+            output,
+            i_pointer: 0,
+            relative_base: 0,
+            state: ComputerState::Continue,
+        }
+    }
+
+    /// Address of the next instruction to be executed
+    pub fn i_pointer(&self) -> usize {
+        self.i_pointer
+    }
+
+    /// Base address for relative mode parameters
+    pub fn relative_base(&self) -> Word {
+        self.relative_base
+    }
+
+    /// Look at the value at `addr` without affecting the computer, or `None` if it's negative
+    pub fn peek(&self, addr: Word) -> Option<Word> {
+        let addr = usize::try_from(addr).ok()?;
+        Some(self.mem.get(addr).copied().unwrap_or(0))
+    }
+
+    /// Set state to error and return Err(err)
+    fn error<T>(&mut self, err: IntcodeError) -> Result<T, IntcodeError> {
+        self.state = ComputerState::Error(err);
+        Err(err)
+    }
+
+    /// Read the value at `addr`, which is 0 if it's past the end of memory
+    pub fn read(&mut self, addr: Word) -> Result<Word, IntcodeError> {
+        match self.peek(addr) {
+            Some(value) => Ok(value),
+            None => self.error(IntcodeError::OutOfBoundsRead {
+                at: self.i_pointer,
+                addr,
+            }),
+        }
+    }
+
+    /// Write `value` to `addr`, growing memory if needed
+    pub fn write(&mut self, addr: Word, value: Word) -> Result<(), IntcodeError> {
+        let Ok(index) = usize::try_from(addr) else {
+            return self.error(IntcodeError::OutOfBoundsWrite {
+                at: self.i_pointer,
+                addr,
+            });
+        };
+        if index >= self.mem.len() {
+            self.mem.resize(index + 1, 0);
+        }
+        self.mem[index] = value;
+        Ok(())
+    }
+
+    /// Get the raw value of an argument of the current instruction, 1-based
+    fn raw_arg(&mut self, arg_num: usize) -> Result<Word, IntcodeError> {
+        self.read((self.i_pointer + arg_num) as Word)
+    }
+
+    /// Get the value of an argument of the current instruction, 1-based
+    fn get_arg(&mut self, ins: &Instruction, arg_num: usize) -> Result<Word, IntcodeError> {
+        let raw = self.raw_arg(arg_num)?;
+        match ins.modes[arg_num - 1] {
+            Mode::Position => self.read(raw),
+            Mode::Immediate => Ok(raw),
+            Mode::Relative => self.read(self.relative_base + raw),
+        }
+    }
+
+    /// Get the address an argument of the current instruction points to, 1-based
+    ///
+    /// Decoding has already checked the argument isn't in immediate mode.
+    fn get_addr(&mut self, ins: &Instruction, arg_num: usize) -> Result<Word, IntcodeError> {
+        let raw = self.raw_arg(arg_num)?;
+        Ok(match ins.modes[arg_num - 1] {
+            Mode::Relative => self.relative_base + raw,
+            _ => raw,
+        })
+    }
+
+    /// Retrieve the instruction at the instruction pointer
+    fn get_instruction(&mut self) -> Result<Instruction, IntcodeError> {
+        let raw = self.read(self.i_pointer as Word)?;
+        match Instruction::from_raw(raw, self.i_pointer) {
+            Ok(ins) => Ok(ins),
+            Err(err) => self.error(err),
+        }
+    }
+
+    /// Step forward one instruction
+    ///
+    /// If the instruction needs input and there isn't any, this returns
+    /// [`IntcodeError::InputExhausted`] and leaves the computer in [`ComputerState::NeedsInput`],
+    /// ready to try the same instruction again.
+    /// Any other error is fatal, and the computer stays in [`ComputerState::Error`].
+    pub fn step(&mut self) -> Result<&ComputerState, IntcodeError> {
+        match self.state {
+            ComputerState::Continue | ComputerState::NeedsInput => {
+                let ins = self.get_instruction()?;
+                let mut next = self.i_pointer + ins.opcode.data_len();
+                self.state = ComputerState::Continue;
+
+                // Execute instruction
+                match ins.opcode {
+                    OpCode::Add | OpCode::Mult | OpCode::LessThan | OpCode::Equals => {
+                        let (l, r) = (self.get_arg(&ins, 1)?, self.get_arg(&ins, 2)?);
+                        let result = match ins.opcode {
+                            OpCode::Add => l + r,
+                            OpCode::Mult => l * r,
+                            OpCode::LessThan => (l < r) as Word,
+                            _ => (l == r) as Word,
+                        };
+                        let addr = self.get_addr(&ins, 3)?;
+                        self.write(addr, result)?;
+                    }
+                    OpCode::Input => match self.input.read() {
+                        Some(value) => {
+                            let addr = self.get_addr(&ins, 1)?;
+                            self.write(addr, value)?;
+                        }
+                        None => {
+                            // Try this instruction again next time
+                            self.state = ComputerState::NeedsInput;
+                            return Err(IntcodeError::InputExhausted { at: self.i_pointer });
+                        }
+                    },
+                    OpCode::Output => {
+                        let value = self.get_arg(&ins, 1)?;
+                        self.output.write(value);
+                    }
+                    OpCode::JumpIfTrue | OpCode::JumpIfFalse => {
+                        let cond = self.get_arg(&ins, 1)? != 0;
+                        if cond == (ins.opcode == OpCode::JumpIfTrue) {
+                            let target = self.get_arg(&ins, 2)?;
+                            match usize::try_from(target) {
+                                Ok(target) => next = target,
+                                Err(_) => self.error(IntcodeError::OutOfBoundsRead {
+                                    at: self.i_pointer,
+                                    addr: target,
+                                })?,
+                            }
+                        }
+                    }
+                    OpCode::AdjustRelativeBase => self.relative_base += self.get_arg(&ins, 1)?,
+                    OpCode::Halt => {
+                        self.state = ComputerState::Halt;
+                        return Ok(&self.state);
+                    }
+                }
+
+                self.i_pointer = next;
+                Ok(&self.state)
+            }
+            // Can't step a halted or error'd computer
+            ComputerState::Halt => Err(IntcodeError::Halted),
+            ComputerState::Error(err) => Err(err),
+        }
+    }
+
+    /// Run until the computer halts or needs more input
+    ///
+    /// Unlike [`IntcodeComputer::step`], running out of input isn't treated as an error: the
+    /// computer just stops in [`ComputerState::NeedsInput`].
+    pub fn run(&mut self) -> Result<&ComputerState, IntcodeError> {
+        loop {
+            match self.step() {
+                Ok(ComputerState::Continue) => {}
+                Ok(_) | Err(IntcodeError::InputExhausted { .. }) => return Ok(&self.state),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
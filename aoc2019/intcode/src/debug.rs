@@ -0,0 +1,246 @@
+use std::fmt;
+
+use crate::computer::{ComputerState, IntcodeComputer};
+use crate::error::IntcodeError;
+use crate::io::{Input, Output};
+use crate::opcode::{Instruction, Mode, OpCode};
+use crate::Word;
+
+/// Somewhere for a [`Debugger`] to stop
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stop before executing the instruction at this address
+    Address(usize),
+    /// Stop before executing any instruction with this opcode
+    OpCode(OpCode),
+}
+
+/// Why a [`Debugger`] stopped running
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stop {
+    /// A breakpoint was hit, before executing the instruction it covers
+    Breakpoint(Breakpoint),
+    NeedsInput,
+    Halt,
+}
+
+/// A record of a single executed instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Address of the instruction
+    pub addr: usize,
+    pub instruction: Instruction,
+    /// `(raw, value)` for each parameter, where `value` is what the parameter resolved to
+    ///
+    /// For the parameter the instruction writes to, `value` is the address written.
+    pub operands: Vec<(Word, Word)>,
+    /// `(address, old value, new value)` for every memory write the instruction made
+    pub writes: Vec<(Word, Word, Word)>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5}: {:<4}",
+            self.addr,
+            self.instruction.opcode.mnemonic()
+        )?;
+        let operands = self
+            .instruction
+            .modes
+            .iter()
+            .zip(&self.operands)
+            .map(|(mode, &(raw, value))| format!("{}={}", mode.format(raw), value))
+            .collect::<Vec<_>>();
+        write!(f, "{}", operands.join(", "))?;
+        for &(addr, old, new) in &self.writes {
+            write!(f, "; [{}]: {} -> {}", addr, old, new)?;
+        }
+        Ok(())
+    }
+}
+
+/// Wraps an [`IntcodeComputer`] to trace its execution and stop at breakpoints
+pub struct Debugger<I, O> {
+    pub computer: IntcodeComputer<I, O>,
+    pub breakpoints: Vec<Breakpoint>,
+    /// Whether to record a [`TraceEntry`] for every instruction executed
+    pub tracing: bool,
+    pub trace: Vec<TraceEntry>,
+    /// The address [`Debugger::run`] last stopped at, whose breakpoints are skipped once on resuming
+    resume_from: Option<usize>,
+}
+
+impl<I: Input, O: Output> Debugger<I, O> {
+    /// Start debugging `computer`, with no breakpoints and tracing on
+    pub fn new(computer: IntcodeComputer<I, O>) -> Debugger<I, O> {
+        Debugger {
+            computer,
+            breakpoints: Vec::new(),
+            tracing: true,
+            trace: Vec::new(),
+            resume_from: None,
+        }
+    }
+
+    /// Work out what the next instruction will do, before it's executed
+    ///
+    /// Returns `None` if it can't be decoded, in which case stepping the computer will fail.
+    fn inspect(&self) -> Option<TraceEntry> {
+        let c = &self.computer;
+        let addr = c.i_pointer();
+        let instruction = Instruction::from_raw(c.peek(addr as Word)?, addr).ok()?;
+        let write_param = instruction.opcode.write_param();
+
+        let mut operands = Vec::new();
+        for (i, mode) in instruction
+            .modes
+            .iter()
+            .enumerate()
+            .take(instruction.opcode.param_count())
+        {
+            let raw = c.peek((addr + i + 1) as Word)?;
+            let target = match mode {
+                Mode::Immediate => None,
+                Mode::Position => Some(raw),
+                Mode::Relative => Some(c.relative_base() + raw),
+            };
+            let value = match target {
+                // Writes show the address, since the old value there is in `writes`
+                Some(target) if write_param == Some(i + 1) => target,
+                Some(target) => c.peek(target)?,
+                None => raw,
+            };
+            operands.push((raw, value));
+        }
+
+        Some(TraceEntry {
+            addr,
+            instruction,
+            operands,
+            writes: Vec::new(),
+        })
+    }
+
+    /// Which breakpoint, if any, covers the next instruction
+    fn breakpoint(&self) -> Option<Breakpoint> {
+        let addr = self.computer.i_pointer();
+        let opcode = self.computer.peek(addr as Word).and_then(OpCode::from_raw);
+        self.breakpoints.iter().copied().find(|&b| match b {
+            Breakpoint::Address(a) => a == addr,
+            Breakpoint::OpCode(op) => Some(op) == opcode,
+        })
+    }
+
+    /// Step forward one instruction, ignoring breakpoints
+    ///
+    /// Errors are the same as for [`IntcodeComputer::step`].
+    pub fn step(&mut self) -> Result<&ComputerState, IntcodeError> {
+        let entry = if self.tracing { self.inspect() } else { None };
+        let written = entry.as_ref().and_then(|e| {
+            let param = e.instruction.opcode.write_param()?;
+            let addr = e.operands[param - 1].1;
+            Some((addr, self.computer.peek(addr)?))
+        });
+
+        self.computer.step()?;
+        if let Some(mut entry) = entry {
+            if let Some((addr, old)) = written {
+                let new = self.computer.peek(addr).unwrap_or_default();
+                entry.writes.push((addr, old, new));
+            }
+            self.trace.push(entry);
+        }
+
+        Ok(&self.computer.state)
+    }
+
+    /// Run until the computer halts, needs more input, or reaches a breakpoint
+    ///
+    /// Breakpoints on the instruction it last stopped on are skipped, so calling this again after
+    /// hitting a breakpoint carries on past it.
+    pub fn run(&mut self) -> Result<Stop, IntcodeError> {
+        let mut resume_from = self.resume_from.take();
+        loop {
+            let addr = self.computer.i_pointer();
+            if resume_from.take() != Some(addr) {
+                if let Some(b) = self.breakpoint() {
+                    self.resume_from = Some(addr);
+                    return Ok(Stop::Breakpoint(b));
+                }
+            }
+
+            match self.step() {
+                Ok(ComputerState::Halt) => return Ok(Stop::Halt),
+                Ok(_) => {}
+                Err(IntcodeError::InputExhausted { .. }) => {
+                    self.resume_from = Some(addr);
+                    return Ok(Stop::NeedsInput);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Overwrites its second instruction with a halt before reaching it
+    const SELF_MODIFYING: [Word; 9] = [1101, 90, 9, 4, 1, 0, 0, 0, 99];
+
+    #[test]
+    fn tracing() {
+        let mut debugger = Debugger::new(IntcodeComputer::new(SELF_MODIFYING.to_vec()));
+        assert_eq!(debugger.run(), Ok(Stop::Halt));
+
+        let trace: Vec<_> = debugger
+            .trace
+            .iter()
+            .map(|e| e.to_string().trim_end().to_string())
+            .collect();
+        assert_eq!(
+            trace,
+            ["    0: add 90=90, 9=9, [4]=4; [4]: 1 -> 99", "    4: hlt"]
+        );
+        assert_eq!(debugger.computer.mem[4], 99);
+    }
+
+    #[test]
+    fn breakpoints() {
+        let program = vec![3, 20, 4, 20, 1101, 2, 3, 21, 4, 21, 99];
+        let mut debugger = Debugger::new(IntcodeComputer::new(program));
+        debugger.tracing = false;
+        debugger.breakpoints = vec![Breakpoint::OpCode(OpCode::Output), Breakpoint::Address(8)];
+
+        assert_eq!(debugger.run(), Ok(Stop::NeedsInput));
+        debugger.computer.input.push_back(7);
+        assert_eq!(
+            debugger.run(),
+            Ok(Stop::Breakpoint(Breakpoint::OpCode(OpCode::Output)))
+        );
+        assert_eq!(debugger.computer.i_pointer(), 2);
+        assert!(debugger.computer.output.is_empty());
+
+        // Address breakpoints take priority, as they're listed first
+        debugger.breakpoints.reverse();
+        assert_eq!(debugger.run(), Ok(Stop::Breakpoint(Breakpoint::Address(8))));
+        assert_eq!(debugger.computer.output, [7]);
+        assert_eq!(debugger.run(), Ok(Stop::Halt));
+        assert_eq!(debugger.computer.output, [7, 5]);
+        assert!(debugger.trace.is_empty());
+    }
+
+    #[test]
+    fn breakpoint_on_first_instruction() {
+        let mut debugger = Debugger::new(IntcodeComputer::new(SELF_MODIFYING.to_vec()));
+        debugger.breakpoints = vec![Breakpoint::Address(0)];
+
+        assert_eq!(debugger.run(), Ok(Stop::Breakpoint(Breakpoint::Address(0))));
+        assert!(debugger.trace.is_empty());
+        assert_eq!(debugger.run(), Ok(Stop::Halt));
+        assert_eq!(debugger.trace.len(), 2);
+    }
+}
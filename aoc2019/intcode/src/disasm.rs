@@ -0,0 +1,98 @@
+use std::fmt;
+
+use crate::opcode::Instruction;
+use crate::Word;
+
+/// A single line of disassembly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Address of the first cell on this line
+    pub addr: usize,
+    /// The decoded instruction, or `None` if the cell isn't a valid one
+    pub instruction: Option<Instruction>,
+    /// Every cell making up the line: the opcode followed by any parameters
+    pub raw: Vec<Word>,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw = self
+            .raw
+            .iter()
+            .map(Word::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{:>5}: {:<24}", self.addr, raw)?;
+
+        match self.instruction {
+            Some(ins) => {
+                write!(f, "{:<4}", ins.opcode.mnemonic())?;
+                let params = ins
+                    .modes
+                    .iter()
+                    .zip(&self.raw[1..])
+                    .map(|(mode, &raw)| mode.format(raw))
+                    .collect::<Vec<_>>();
+                write!(f, "{}", params.join(", "))
+            }
+            None => write!(f, "data"),
+        }
+    }
+}
+
+/// Disassemble a memory image, one instruction per line
+///
+/// This is a linear sweep, so data mixed in with code may be decoded as instructions, and
+/// self-modifying code is shown as it stands in `mem`.
+/// Cells which don't decode to an instruction, or whose instruction would run off the end of
+/// memory, are shown as single cells of data.
+pub fn disassemble(mem: &[Word]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut addr = 0;
+
+    while addr < mem.len() {
+        let instruction = Instruction::from_raw(mem[addr], addr)
+            .ok()
+            .filter(|ins| addr + ins.opcode.data_len() <= mem.len());
+        let len = instruction.map_or(1, |ins| ins.opcode.data_len());
+        lines.push(Line {
+            addr,
+            instruction,
+            raw: mem[addr..addr + len].to_vec(),
+        });
+        addr += len;
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listing() {
+        let lines: Vec<_> = disassemble(&[1002, 4, 3, 4, 33, 109, -2, 203, 7, 42, 99])
+            .iter()
+            .map(|l| l.to_string().trim_end().to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "    0: 1002,4,3,4              mul [4], 3, [4]",
+                "    4: 33                      data",
+                "    5: 109,-2                  arb -2",
+                "    7: 203,7                   in  [rb+7]",
+                "    9: 42                      data",
+                "   10: 99                      hlt",
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let lines = disassemble(&[1, 0, 0]);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|l| l.instruction.is_none()));
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::Word;
+
+/// Everything that can go wrong running an [`IntcodeComputer`](crate::IntcodeComputer)
+///
+/// `at` is always the address of the instruction that caused the error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IntcodeError {
+    /// The value at `at` isn't a known opcode
+    InvalidOpcode { at: usize, value: Word },
+    /// Parameter `param` (1-based) of the instruction has a mode that doesn't exist, or is
+    /// immediate mode for a parameter that's written to
+    BadMode { at: usize, param: usize, mode: u8 },
+    /// Tried to read from a negative address, including by jumping to one
+    OutOfBoundsRead { at: usize, addr: Word },
+    /// Tried to write to a negative address
+    OutOfBoundsWrite { at: usize, addr: Word },
+    /// An input instruction ran with no input available
+    ///
+    /// This is the only error a computer can recover from: nothing has been executed, so once
+    /// more input has been provided it can carry on from where it stopped.
+    InputExhausted { at: usize },
+    /// The computer has already halted
+    Halted,
+}
+
+impl IntcodeError {
+    /// Whether the computer can carry on after this error
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::InputExhausted { .. })
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOpcode { at, value } => {
+                write!(f, "Invalid instruction value {} at {}", value, at)
+            }
+            Self::BadMode { at, param, mode } => write!(
+                f,
+                "Invalid mode {} for parameter {} of instruction at {}",
+                mode, param, at
+            ),
+            Self::OutOfBoundsRead { at, addr } => write!(
+                f,
+                "Instruction at {} tried to read from address {}",
+                at, addr
+            ),
+            Self::OutOfBoundsWrite { at, addr } => write!(
+                f,
+                "Instruction at {} tried to write to address {}",
+                at, addr
+            ),
+            Self::InputExhausted { at } => write!(f, "Instruction at {} is waiting for input", at),
+            Self::Halted => write!(f, "Computer is halted"),
+        }
+    }
+}
+
+impl std::error::Error for IntcodeError {}
@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+use crate::Word;
+
+/// Somewhere an [`IntcodeComputer`](crate::IntcodeComputer) can read input from
+pub trait Input {
+    /// Take the next value, or `None` if there isn't one available yet
+    fn read(&mut self) -> Option<Word>;
+}
+
+/// Somewhere an [`IntcodeComputer`](crate::IntcodeComputer) can write output to
+pub trait Output {
+    fn write(&mut self, value: Word);
+}
+
+impl Input for VecDeque<Word> {
+    fn read(&mut self) -> Option<Word> {
+        self.pop_front()
+    }
+}
+
+impl Output for VecDeque<Word> {
+    fn write(&mut self, value: Word) {
+        self.push_back(value)
+    }
+}
+
+impl Output for Vec<Word> {
+    fn write(&mut self, value: Word) {
+        self.push(value)
+    }
+}
+
+impl<T: Input + ?Sized> Input for &mut T {
+    fn read(&mut self) -> Option<Word> {
+        (**self).read()
+    }
+}
+
+impl<T: Output + ?Sized> Output for &mut T {
+    fn write(&mut self, value: Word) {
+        (**self).write(value)
+    }
+}
+
+/// Input from any iterator
+///
+/// Once the iterator runs out, the computer will wait for input forever.
+#[derive(Debug, Clone)]
+pub struct IterInput<I>(pub I);
+
+impl<I: Iterator<Item = Word>> Input for IterInput<I> {
+    fn read(&mut self) -> Option<Word> {
+        self.0.next()
+    }
+}
+
+/// Output to any closure
+pub struct FnOutput<F>(pub F);
+
+impl<F: FnMut(Word)> Output for FnOutput<F> {
+    fn write(&mut self, value: Word) {
+        (self.0)(value)
+    }
+}
@@ -0,0 +1,164 @@
+//! An Intcode computer, as used throughout Advent of Code 2019
+//!
+//! Machines read input from anything implementing [`Input`] and write output to anything
+//! implementing [`Output`], and [`IntcodeComputer::run`] stops whenever a machine needs more input,
+//! so several machines can be chained together by passing output from one to the next.
+//!
+//! For working out what a program does, [`disassemble`] lists a memory image as mnemonics, and a
+//! [`Debugger`] traces every instruction executed and stops at breakpoints.
+
+mod computer;
+mod debug;
+mod disasm;
+mod error;
+mod io;
+mod opcode;
+
+pub use computer::{ComputerState, IntcodeComputer};
+pub use debug::{Breakpoint, Debugger, Stop, TraceEntry};
+pub use disasm::{disassemble, Line};
+pub use error::IntcodeError;
+pub use io::{FnOutput, Input, IterInput, Output};
+pub use opcode::{get_digit, Instruction, Mode, OpCode};
+
+use std::num::ParseIntError;
+
+/// A single memory cell
+pub type Word = i64;
+
+/// Parse a comma-separated Intcode program
+pub fn parse_program(raw: &str) -> Result<Vec<Word>, ParseIntError> {
+    raw.trim().split(',').map(|x| x.trim().parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `program` to completion on `inputs`, returning the final memory and all output
+    fn run(program: &[Word], inputs: &[Word]) -> (Vec<Word>, Vec<Word>) {
+        let mut computer = IntcodeComputer::new(program.to_vec());
+        computer.input.extend(inputs);
+        assert_eq!(computer.run(), Ok(&ComputerState::Halt));
+        (computer.mem, computer.output.into())
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            run(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], &[]).0,
+            [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+        );
+        assert_eq!(run(&[1002, 4, 3, 4, 33], &[]).0, [1002, 4, 3, 4, 99]);
+        assert_eq!(run(&[1101, 100, -1, 4, 0], &[]).0, [1101, 100, -1, 4, 99]);
+    }
+
+    #[test]
+    fn input_goes_to_the_pointed_cell() {
+        assert_eq!(run(&[3, 3, 99, 0], &[42]).0, [3, 3, 99, 42]);
+        assert_eq!(run(&[3, 0, 4, 0, 99], &[-7]).1, [-7]);
+    }
+
+    #[test]
+    fn comparisons_and_jumps() {
+        // Is the input equal to 8, in position and immediate mode
+        for program in [
+            &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8][..],
+            &[3, 3, 1108, -1, 8, 3, 4, 3, 99],
+        ] {
+            assert_eq!(run(program, &[8]).1, [1]);
+            assert_eq!(run(program, &[7]).1, [0]);
+        }
+
+        let compare_8 = parse_program(
+            "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99",
+        )
+        .unwrap();
+        assert_eq!(run(&compare_8, &[7]).1, [999]);
+        assert_eq!(run(&compare_8, &[8]).1, [1000]);
+        assert_eq!(run(&compare_8, &[9]).1, [1001]);
+    }
+
+    #[test]
+    fn relative_base_and_large_memory() {
+        let quine = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run(&quine, &[]).1, quine);
+        assert_eq!(
+            run(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[]).1,
+            [1219070632396864]
+        );
+        assert_eq!(run(&[104, 1125899906842624, 99], &[]).1, [1125899906842624]);
+    }
+
+    #[test]
+    fn waits_for_input() {
+        let mut computer = IntcodeComputer::new(vec![3, 20, 3, 21, 1, 20, 21, 22, 4, 22, 99]);
+        assert_eq!(computer.run(), Ok(&ComputerState::NeedsInput));
+        computer.input.push_back(2);
+        assert_eq!(computer.run(), Ok(&ComputerState::NeedsInput));
+
+        // Stepping reports the wait as an error, but can still be resumed
+        let err = computer.step().unwrap_err();
+        assert_eq!(err, IntcodeError::InputExhausted { at: 2 });
+        assert!(err.is_recoverable());
+        assert_eq!(computer.state, ComputerState::NeedsInput);
+
+        computer.input.push_back(3);
+        assert_eq!(computer.run(), Ok(&ComputerState::Halt));
+        assert_eq!(computer.output.pop_front(), Some(5));
+        assert_eq!(computer.step(), Err(IntcodeError::Halted));
+    }
+
+    #[test]
+    fn chained_feedback_loop() {
+        let program = parse_program(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        )
+        .unwrap();
+        let mut amps: Vec<_> = [9, 8, 7, 6, 5]
+            .into_iter()
+            .map(|phase| {
+                let mut amp = IntcodeComputer::new(program.clone());
+                amp.input.push_back(phase);
+                amp
+            })
+            .collect();
+
+        let mut signal = 0;
+        while amps[4].state != ComputerState::Halt {
+            for amp in &mut amps {
+                amp.input.push_back(signal);
+                amp.run().unwrap();
+                signal = amp.output.pop_back().unwrap();
+            }
+        }
+        assert_eq!(signal, 139629729);
+    }
+
+    #[test]
+    fn pluggable_io() {
+        let mut seen = Vec::new();
+        let mut computer = IntcodeComputer::with_io(
+            vec![3, 0, 4, 0, 3, 0, 4, 0, 99],
+            IterInput([5, 6].into_iter()),
+            FnOutput(|x| seen.push(x * 10)),
+        );
+        assert_eq!(computer.run(), Ok(&ComputerState::Halt));
+        drop(computer);
+        assert_eq!(seen, [50, 60]);
+    }
+
+    #[test]
+    fn errors() {
+        let mut computer = IntcodeComputer::new(vec![42]);
+        assert!(computer.step().is_err());
+        assert!(matches!(computer.state, ComputerState::Error(_)));
+
+        assert!(IntcodeComputer::new(vec![4, -1, 99]).run().is_err());
+        assert!(IntcodeComputer::new(vec![11101, 1, 1, 0, 99])
+            .run()
+            .is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::IntcodeError;
+use crate::Word;
+
+/// Get (0-based) digit from number
+pub fn get_digit(val: Word, digit: u32) -> u8 {
+    (val.unsigned_abs() / 10_u64.pow(digit) % 10) as u8
+}
+
+/// How an instruction's parameter is interpreted
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// The parameter is the address of the value
+    Position,
+    /// The parameter is the value itself
+    Immediate,
+    /// The parameter is an address, offset by the relative base
+    Relative,
+}
+
+impl Mode {
+    /// Format a raw parameter in this mode: `[addr]`, `value` or `[rb+offset]`
+    pub fn format(&self, raw: Word) -> String {
+        match self {
+            Self::Position => format!("[{}]", raw),
+            Self::Immediate => format!("{}", raw),
+            Self::Relative => format!("[rb{:+}]", raw),
+        }
+    }
+
+    pub fn from_raw(digit: u8) -> Option<Mode> {
+        match digit {
+            0 => Some(Self::Position),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OpCode {
+    Add,
+    Mult,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+}
+
+impl OpCode {
+    pub fn from_raw(code: Word) -> Option<OpCode> {
+        match code % 100 {
+            1 => Some(Self::Add),
+            2 => Some(Self::Mult),
+            3 => Some(Self::Input),
+            4 => Some(Self::Output),
+            5 => Some(Self::JumpIfTrue),
+            6 => Some(Self::JumpIfFalse),
+            7 => Some(Self::LessThan),
+            8 => Some(Self::Equals),
+            9 => Some(Self::AdjustRelativeBase),
+            99 => Some(Self::Halt),
+            _ => None,
+        }
+    }
+
+    /// Number of memory cells taken up by the instruction, including the opcode itself
+    pub fn data_len(&self) -> usize {
+        self.param_count() + 1
+    }
+
+    /// Which parameter (1-based) the instruction writes its result to, if any
+    pub fn write_param(&self) -> Option<usize> {
+        match self {
+            Self::Add | Self::Mult | Self::LessThan | Self::Equals => Some(3),
+            Self::Input => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Short name for the opcode, as used by the disassembler
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Mult => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jt",
+            Self::JumpIfFalse => "jf",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::AdjustRelativeBase => "arb",
+            Self::Halt => "hlt",
+        }
+    }
+
+    pub fn param_count(&self) -> usize {
+        match self {
+            Self::Add | Self::Mult | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::AdjustRelativeBase => 1,
+            Self::Halt => 0,
+        }
+    }
+}
+
+/// A decoded instruction: the opcode along with the mode of each of its parameters
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: OpCode,
+    pub modes: [Mode; 3],
+}
+
+impl Instruction {
+    /// Decode the instruction `raw`, found at address `at`
+    pub fn from_raw(raw: Word, at: usize) -> Result<Instruction, IntcodeError> {
+        let opcode = OpCode::from_raw(raw).ok_or(IntcodeError::InvalidOpcode { at, value: raw })?;
+        let mut modes = [Mode::Position; 3];
+        for (i, mode) in modes.iter_mut().enumerate().take(opcode.param_count()) {
+            let digit = get_digit(raw, i as u32 + 2);
+            *mode = Mode::from_raw(digit).ok_or(IntcodeError::BadMode {
+                at,
+                param: i + 1,
+                mode: digit,
+            })?;
+        }
+        // Results can't be written to a value
+        if let Some(param) = opcode.write_param() {
+            if modes[param - 1] == Mode::Immediate {
+                return Err(IntcodeError::BadMode { at, param, mode: 1 });
+            }
+        }
+        Ok(Instruction { opcode, modes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_digit() {
+        assert_eq!(get_digit(12345, 2), 3);
+        assert_eq!(get_digit(100, 1), 0);
+        assert_eq!(get_digit(123421, 8), 0);
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            Instruction::from_raw(1002, 0),
+            Ok(Instruction {
+                opcode: OpCode::Mult,
+                modes: [Mode::Position, Mode::Immediate, Mode::Position]
+            })
+        );
+        assert_eq!(
+            Instruction::from_raw(204, 0).map(|i| i.modes[0]),
+            Ok(Mode::Relative)
+        );
+        assert_eq!(
+            Instruction::from_raw(301, 5),
+            Err(IntcodeError::BadMode {
+                at: 5,
+                param: 1,
+                mode: 3
+            })
+        );
+        assert_eq!(
+            Instruction::from_raw(11101, 2),
+            Err(IntcodeError::BadMode {
+                at: 2,
+                param: 3,
+                mode: 1
+            })
+        );
+        assert_eq!(
+            Instruction::from_raw(42, 7),
+            Err(IntcodeError::InvalidOpcode { at: 7, value: 42 })
+        );
+    }
+}
@@ -95,21 +95,11 @@ mod tests {
 
     #[test]
     fn my_input_part1() {
-        assert_eq!(
-            solve_input_part1(&parse_input(
-                &std::fs::read_to_string("./input/2020/day2.txt").unwrap()
-            )),
-            474
-        );
+        crate::check_answer(2, 1, |input| solve_input_part1(&parse_input(input)));
     }
 
     #[test]
     fn my_input_part2() {
-        assert_eq!(
-            solve_input_part2(&parse_input(
-                &std::fs::read_to_string("./input/2020/day2.txt").unwrap()
-            )),
-            745
-        );
+        crate::check_answer(2, 2, |input| solve_input_part2(&parse_input(input)));
     }
 }
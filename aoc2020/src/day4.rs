@@ -246,11 +246,6 @@ iyr:2011 ecl:brn hgt:59in"
 
     #[test]
     fn my_data_part1() {
-        assert_eq!(
-            solve_input_part1(&parse_input(
-                &std::fs::read_to_string("./input/2020/day4.txt").unwrap()
-            )),
-            260
-        );
+        crate::check_answer(4, 1, |input| solve_input_part1(&parse_input(input)));
     }
 }
@@ -58,22 +58,12 @@ mod tests {
 
     #[test]
     fn my_input_part1() {
-        assert_eq!(
-            solve_input_part1(&parse_input(
-                &std::fs::read_to_string("./input/2020/day5.txt").unwrap()
-            )),
-            994
-        );
+        crate::check_answer(5, 1, |input| solve_input_part1(&parse_input(input)));
     }
 
     #[test]
     fn my_input_part2() {
-        assert_eq!(
-            solve_input_part2(&parse_input(
-                &std::fs::read_to_string("./input/2020/day5.txt").unwrap()
-            )),
-            741
-        );
+        crate::check_answer(5, 2, |input| solve_input_part2(&parse_input(input)));
     }
 
     #[test_case("FBFBBFFRLR", 44, 5, 357)]
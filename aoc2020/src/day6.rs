@@ -77,21 +77,11 @@ mod tests {
 
     #[test]
     fn my_input_part1() {
-        assert_eq!(
-            solve_input_part1(&parse_input(
-                &std::fs::read_to_string("./input/2020/day6.txt").unwrap()
-            )),
-            6782
-        );
+        crate::check_answer(6, 1, |input| solve_input_part1(&parse_input(input)));
     }
 
     #[test]
     fn my_input_part2() {
-        assert_eq!(
-            solve_input_part2(&parse_input(
-                &std::fs::read_to_string("./input/2020/day6.txt").unwrap()
-            )),
-            3596
-        );
+        crate::check_answer(6, 2, |input| solve_input_part2(&parse_input(input)));
     }
 }
@@ -18,3 +18,9 @@ pub fn list_of_numbers(input: &str) -> Vec<u32> {
 }
 
 aoc_lib! { year = 2020 }
+
+/// Check a solution against my recorded answer, see [`aoc_helpers::answers::assert_answer`]
+#[cfg(test)]
+fn check_answer<T: std::fmt::Display>(day: u32, part: u32, solve: impl FnOnce(&str) -> T) {
+    aoc_helpers::answers::assert_answer(2020, day, part, solve);
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use super::BITSOperator;
+
+/// Everything that can go wrong decoding a BITS transmission
+///
+/// `offset` is always the bit offset, from the start of the transmission, of the field being read
+/// when the problem was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitsError {
+    /// The hex digit at character `index` isn't one
+    InvalidHex { index: usize, found: char },
+    /// A field of `wanted` bits runs past the end of the transmission
+    UnexpectedEnd { offset: usize, wanted: usize },
+    /// A literal has too many groups to fit in a `usize`
+    LiteralOverflow { offset: usize },
+    /// The subpackets of a length type 0 operator didn't end exactly where its length said they
+    /// would, instead ending at `actual`
+    LengthMismatch {
+        offset: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// An operator has a number of subpackets it can't be evaluated with
+    OperandCount {
+        offset: usize,
+        operator: BITSOperator,
+        count: usize,
+    },
+}
+
+impl fmt::Display for BitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHex { index, found } => {
+                write!(f, "Non-hex character {:?} at index {}", found, index)
+            }
+            Self::UnexpectedEnd { offset, wanted } => write!(
+                f,
+                "Bit {}: transmission ended while reading a {} bit field",
+                offset, wanted
+            ),
+            Self::LiteralOverflow { offset } => {
+                write!(f, "Bit {}: literal is too large", offset)
+            }
+            Self::LengthMismatch {
+                offset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Bit {}: subpackets should have ended at bit {}, but ended at {}",
+                offset, expected, actual
+            ),
+            Self::OperandCount {
+                offset,
+                operator,
+                count,
+            } => write!(
+                f,
+                "Bit {}: {:?} operator can't take {} subpackets",
+                offset, operator, count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BitsError {}
+
+/// Packets which can't be written as a BITS transmission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// Versions are only 3 bits
+    VersionTooLarge { version: u8 },
+    /// An operator has a number of subpackets it can't be evaluated with
+    OperandCount {
+        operator: BITSOperator,
+        count: usize,
+    },
+    /// An operator's subpackets don't fit in the length field of any length type the policy
+    /// allows
+    TooLong { count: usize, bits: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VersionTooLarge { version } => {
+                write!(f, "Version {} doesn't fit in 3 bits", version)
+            }
+            Self::OperandCount { operator, count } => {
+                write!(f, "{:?} operator can't take {} subpackets", operator, count)
+            }
+            Self::TooLong { count, bits } => write!(
+                f,
+                "{} subpackets taking up {} bits don't fit in the length field",
+                count, bits
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
@@ -0,0 +1,182 @@
+//! Ways of looking inside a packet tree, as used by the `bits_decode` binary
+
+use std::fmt::{self, Write};
+
+use super::{BITSOperator, BITSPacket, BITSPacketVersioned};
+
+/// One operator being applied during evaluation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// How deeply nested the operator is, where the outermost packet is at depth 0
+    pub depth: usize,
+    pub operator: BITSOperator,
+    /// Values of each of the subpackets
+    pub operands: Vec<usize>,
+    pub result: usize,
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}({}",
+            "",
+            self.operator.name(),
+            indent = self.depth * 2
+        )?;
+        for operand in &self.operands {
+            write!(f, " {}", operand)?;
+        }
+        write!(f, ") = {}", self.result)
+    }
+}
+
+/// Counts of everything in a packet tree
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub packets: usize,
+    pub literals: usize,
+    /// Number of each operator, in the order of [`BITSOperator::ALL`]
+    pub operators: [usize; 7],
+    /// Number of packets at each depth, starting with the outermost packet
+    pub per_depth: Vec<usize>,
+    pub version_sum: usize,
+}
+
+impl Stats {
+    pub fn max_depth(&self) -> usize {
+        self.per_depth.len() - 1
+    }
+
+    fn add(&mut self, packet: &BITSPacketVersioned, depth: usize) {
+        self.packets += 1;
+        self.version_sum += packet.version as usize;
+        if self.per_depth.len() <= depth {
+            self.per_depth.push(0);
+        }
+        self.per_depth[depth] += 1;
+
+        match &packet.packet {
+            BITSPacket::Literal(_) => self.literals += 1,
+            BITSPacket::Operator(op, subs) => {
+                let index = BITSOperator::ALL.iter().position(|o| o == op).unwrap();
+                self.operators[index] += 1;
+                for sub in subs {
+                    self.add(sub, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "packets:     {}", self.packets)?;
+        writeln!(f, "literals:    {}", self.literals)?;
+        for (op, count) in BITSOperator::ALL.iter().zip(self.operators) {
+            writeln!(f, "{:<12} {}", format!("{}:", op.name()), count)?;
+        }
+        writeln!(f, "version sum: {}", self.version_sum)?;
+        writeln!(f, "max depth:   {}", self.max_depth())?;
+        for (depth, count) in self.per_depth.iter().enumerate() {
+            writeln!(f, "  depth {:>3}: {}", depth, count)?;
+        }
+        Ok(())
+    }
+}
+
+impl BITSPacketVersioned {
+    /// Render the tree one packet per line, with subpackets indented below their operator
+    pub fn pretty_tree(&self) -> String {
+        let mut out = String::new();
+        self.write_tree(&mut out, 0);
+        out
+    }
+
+    fn write_tree(&self, out: &mut String, depth: usize) {
+        let indent = depth * 2;
+        match &self.packet {
+            BITSPacket::Literal(value) => {
+                writeln!(out, "{:indent$}{} (v{})", "", value, self.version).unwrap()
+            }
+            BITSPacket::Operator(op, subs) => {
+                writeln!(out, "{:indent$}{} (v{})", "", op.name(), self.version).unwrap();
+                for sub in subs {
+                    sub.write_tree(out, depth + 1);
+                }
+            }
+        }
+    }
+
+    /// Evaluate the packet, recording each operator as it's applied, innermost first
+    pub fn eval_trace(&self) -> (usize, Vec<TraceStep>) {
+        let mut trace = Vec::new();
+        let value = self.eval_traced(0, &mut trace);
+        (value, trace)
+    }
+
+    fn eval_traced(&self, depth: usize, trace: &mut Vec<TraceStep>) -> usize {
+        match &self.packet {
+            BITSPacket::Literal(value) => *value,
+            BITSPacket::Operator(op, subs) => {
+                let operands: Vec<_> = subs
+                    .iter()
+                    .map(|sub| sub.eval_traced(depth + 1, trace))
+                    .collect();
+                let result = op.apply(operands.iter().copied());
+                trace.push(TraceStep {
+                    depth,
+                    operator: *op,
+                    operands,
+                    result,
+                });
+                result
+            }
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        stats.add(self, 0);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree() {
+        let packet: BITSPacketVersioned = "(sum (product 6 9) 3)".parse().unwrap();
+        assert_eq!(
+            packet.pretty_tree(),
+            "sum (v0)\n  product (v0)\n    6 (v0)\n    9 (v0)\n  3 (v0)\n"
+        );
+    }
+
+    #[test]
+    fn trace() {
+        let packet: BITSPacketVersioned = "(sum (product 6 9) (min 3 4))".parse().unwrap();
+        let (value, trace) = packet.eval_trace();
+        assert_eq!(value, 57);
+        let lines: Vec<_> = trace.iter().map(TraceStep::to_string).collect();
+        assert_eq!(
+            lines,
+            ["  (product 6 9) = 54", "  (min 3 4) = 3", "(sum 54 3) = 57"]
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let stats = crate::bits::parse("A0016C880162017C3686B18A3D4780")
+            .unwrap()
+            .stats();
+        assert_eq!(stats.packets, 8);
+        assert_eq!(stats.literals, 5);
+        assert_eq!(stats.operators, [3, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(stats.per_depth, [1, 1, 1, 5]);
+        assert_eq!(stats.max_depth(), 3);
+        assert_eq!(stats.version_sum, 31);
+    }
+}
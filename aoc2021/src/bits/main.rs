@@ -3,14 +3,29 @@ use std::error::Error;
 use std::fs;
 use std::time::Instant;
 
-use aoc2021::bits;
+use aoc2021::bits::{self, BITSPacketVersioned, LengthPolicy, LengthType};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input_file_path = args().nth(1).expect("No input file path provided");
-    let input = fs::read_to_string(input_file_path)?;
+const USAGE: &str = "Usage:
+    bits_decode [summary] FILE    parse and evaluate, with timings
+    bits_decode tree FILE         print the packet tree, one packet per line
+    bits_decode sexpr FILE        print the packet tree as an S-expression
+    bits_decode trace FILE        show each operator as it's evaluated
+    bits_decode stats FILE        count packets by type and depth
+    bits_decode encode [--length-type bits|count|shortest] EXPR
+                                  encode an S-expression like '(sum (product 6 9) 3)' as hex";
+
+const COMMANDS: [&str; 6] = ["summary", "tree", "sexpr", "trace", "stats", "encode"];
+
+fn read_packet(path: &str) -> Result<BITSPacketVersioned, Box<dyn Error>> {
+    let input = fs::read_to_string(path)?;
+    Ok(bits::parse(input.trim())?)
+}
+
+fn summary(path: &str) -> Result<(), Box<dyn Error>> {
+    let input = fs::read_to_string(path)?;
 
     let now = Instant::now();
-    let bit_packets = bits::parse(&bits::get_bits(input.trim())).0;
+    let bit_packets = bits::parse(input.trim())?;
     let parse_time = now.elapsed().as_micros();
 
     let now = Instant::now();
@@ -29,3 +44,51 @@ The total version was {}, calculated in {}ns.",
     );
     Ok(())
 }
+
+fn encode(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (policy, expr) = match args {
+        [flag, length_type, expr] if flag == "--length-type" => {
+            let policy = match length_type.as_str() {
+                "bits" => LengthPolicy::Always(LengthType::TotalBits),
+                "count" => LengthPolicy::Always(LengthType::SubpacketCount),
+                "shortest" => LengthPolicy::Shortest,
+                other => return Err(format!("Unknown length type {:?}", other).into()),
+            };
+            (policy, expr)
+        }
+        [expr] => (LengthPolicy::default(), expr),
+        _ => return Err(USAGE.into()),
+    };
+
+    let packet: BITSPacketVersioned = expr.parse()?;
+    println!("{}", bits::encode(&packet, policy)?);
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["tree", path] => print!("{}", read_packet(path)?.pretty_tree()),
+        ["sexpr", path] => println!("{}", read_packet(path)?),
+        ["trace", path] => {
+            let (value, trace) = read_packet(path)?.eval_trace();
+            for step in trace {
+                println!("{}", step);
+            }
+            println!("Result: {}", value);
+        }
+        ["stats", path] => print!("{}", read_packet(path)?.stats()),
+        ["encode", ..] => encode(&args[1..])?,
+        ["summary", path] => summary(path)?,
+        [path] if !COMMANDS.contains(&path) && !path.starts_with('-') => summary(path)?,
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
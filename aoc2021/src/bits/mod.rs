@@ -1,3 +1,15 @@
+mod error;
+mod inspect;
+mod reader;
+mod sexpr;
+mod writer;
+
+pub use error::{BitsError, EncodeError};
+pub use inspect::{Stats, TraceStep};
+pub use reader::BitReader;
+pub use sexpr::SexprError;
+pub use writer::BitWriter;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BITSPacketVersioned {
     pub version: u8,
@@ -10,7 +22,7 @@ pub enum BITSPacket {
     Operator(BITSOperator, Vec<BITSPacketVersioned>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BITSOperator {
     Sum,
     Product,
@@ -21,168 +33,260 @@ pub enum BITSOperator {
     Equal,
 }
 
-pub fn get_bits(hex_raw: &str) -> Vec<bool> {
-    hex_raw
-        .bytes()
-        .map(|c| match c {
-            // TODO: this is kinda awful, but actual bit manipulation
-            // is difficult because the input isn't byte-aligned
-            b'0' => [false, false, false, false],
-            b'1' => [false, false, false, true],
-            b'2' => [false, false, true, false],
-            b'3' => [false, false, true, true],
-            b'4' => [false, true, false, false],
-            b'5' => [false, true, false, true],
-            b'6' => [false, true, true, false],
-            b'7' => [false, true, true, true],
-            b'8' => [true, false, false, false],
-            b'9' => [true, false, false, true],
-            b'A' => [true, false, true, false],
-            b'B' => [true, false, true, true],
-            b'C' => [true, true, false, false],
-            b'D' => [true, true, false, true],
-            b'E' => [true, true, true, false],
-            b'F' => [true, true, true, true],
-            _ => panic!("Non-hex character in input"),
-        })
-        .flatten()
-        .collect()
-}
+/// Type ID of a literal value packet
+const LITERAL_TYPE: usize = 4;
 
-pub fn bits_to_num(raw: &[bool]) -> usize {
-    raw.iter()
-        .rev()
-        .enumerate()
-        .map(|(i, b)| (*b as usize) << i)
-        .sum()
+/// Which of the two ways of giving the length of an operator's subpackets to use
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LengthType {
+    /// Type 0: the total number of bits, in 15 bits
+    TotalBits,
+    /// Type 1: the number of subpackets, in 11 bits
+    SubpacketCount,
 }
 
-pub fn format_bits(bits: &[bool]) -> String {
-    let bits_str: Vec<String> = bits
-        .iter()
-        .map(|b| if *b { "1" } else { "0" }.into())
-        .collect();
+/// How the encoder picks a [`LengthType`] for each operator
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum LengthPolicy {
+    /// Always use this length type, failing if the subpackets don't fit in it
+    Always(LengthType),
+    /// Use whichever length type takes fewer bits, which is the subpacket count unless there are
+    /// more than 2047 subpackets
+    #[default]
+    Shortest,
+}
 
-    bits_str.join("")
+impl LengthType {
+    /// Width of the length field
+    fn width(&self) -> usize {
+        match self {
+            Self::TotalBits => 15,
+            Self::SubpacketCount => 11,
+        }
+    }
 }
 
-pub fn parse(bits: &[bool]) -> (BITSPacketVersioned, usize) {
-    let version: u8 = bits_to_num(&bits[0..3]) as u8;
-    let type_id: u8 = bits_to_num(&bits[3..6]) as u8;
-
-    #[cfg(test)]
-    println!(
-        "{}: version {}, type {}",
-        format_bits(bits),
-        version,
-        type_id
-    );
-
-    // Keeps track of what bit in the sequence we're looking at
-    let mut ctr = 6;
-
-    let packet_type = match type_id {
-        // Literal value
-        4 => BITSPacket::Literal({
-            // TODO: change me to a fixed-size buffer?
-            let mut lit_groups = Vec::with_capacity(24);
-
-            while let [true, vals @ ..] = &bits[ctr..(ctr + 5)] {
-                lit_groups.extend_from_slice(vals);
-                ctr += 5;
-            }
+impl BITSOperator {
+    pub const ALL: [BITSOperator; 7] = [
+        BITSOperator::Sum,
+        BITSOperator::Product,
+        BITSOperator::Minimum,
+        BITSOperator::Maximum,
+        BITSOperator::Greater,
+        BITSOperator::Less,
+        BITSOperator::Equal,
+    ];
+
+    fn type_id(&self) -> usize {
+        use BITSOperator::*;
 
-            // NOTE: skips the first bit because we know it's 0
-            lit_groups.extend_from_slice(&bits[(ctr + 1)..(ctr + 5)]);
-            ctr += 5;
-
-            bits_to_num(&lit_groups)
-        }),
-        // Operator
-        op => {
-            let op_type = match op {
-                0 => BITSOperator::Sum,
-                1 => BITSOperator::Product,
-                2 => BITSOperator::Minimum,
-                3 => BITSOperator::Maximum,
-                5 => BITSOperator::Greater,
-                6 => BITSOperator::Less,
-                7 => BITSOperator::Equal,
-                _ => panic!("Unknown opcode: {}", op),
-            };
+        match self {
+            Sum => 0,
+            Product => 1,
+            Minimum => 2,
+            Maximum => 3,
+            Greater => 5,
+            Less => 6,
+            Equal => 7,
+        }
+    }
 
-            let subpackets = if bits[6] {
-                // Next 11 bits is the number of subpackets
-                let num_subpackets = bits_to_num(&bits[7..18]);
-                ctr += 12;
+    fn from_type_id(type_id: usize) -> Option<BITSOperator> {
+        match type_id {
+            0 => Some(Self::Sum),
+            1 => Some(Self::Product),
+            2 => Some(Self::Minimum),
+            3 => Some(Self::Maximum),
+            5 => Some(Self::Greater),
+            6 => Some(Self::Less),
+            7 => Some(Self::Equal),
+            _ => None,
+        }
+    }
 
-                #[cfg(test)]
-                println!(
-                    "Detected operator {} with {} subpackets",
-                    op, num_subpackets
-                );
+    /// Whether the operator can be evaluated with `count` subpackets
+    fn takes_operands(&self, count: usize) -> bool {
+        use BITSOperator::*;
 
-                let mut subpackets = Vec::with_capacity(num_subpackets);
+        match self {
+            Sum | Product => true,
+            Minimum | Maximum => count > 0,
+            Greater | Less | Equal => count == 2,
+        }
+    }
+}
 
-                while subpackets.len() < num_subpackets {
-                    let (subp, subplen) = parse(&bits[ctr..]);
+/// Decode a hex transmission, ignoring any padding after the outermost packet
+pub fn parse(hex: &str) -> Result<BITSPacketVersioned, BitsError> {
+    parse_packet(&mut BitReader::new(hex))
+}
 
-                    ctr += subplen;
-                    subpackets.push(subp);
+/// Decode the packet starting at the reader's position, leaving the reader just after it
+pub fn parse_packet(reader: &mut BitReader) -> Result<BITSPacketVersioned, BitsError> {
+    let start = reader.position();
+    let version = reader.read(3)? as u8;
+    let type_id = reader.read(3)?;
+
+    let packet = match BITSOperator::from_type_id(type_id) {
+        // Every type ID apart from the literal one is an operator
+        None => BITSPacket::Literal(parse_literal(reader)?),
+        Some(op) => {
+            let mut subpackets = Vec::new();
+            if reader.read_bool()? {
+                // Next 11 bits is the number of subpackets
+                let num_subpackets = reader.read(11)?;
+                for _ in 0..num_subpackets {
+                    subpackets.push(parse_packet(reader)?);
                 }
-
-                subpackets
             } else {
                 // Next 15 bits is length of subpackets
-                let len_subpackets = bits_to_num(&bits[7..22]);
-                ctr += 16;
-                let sub_start = ctr;
-
-                #[cfg(test)]
-                println!(
-                    "Detected operator {} with {} BITS of subpackets",
-                    op, len_subpackets
-                );
-
-                let mut subpackets = Vec::with_capacity(10);
-                while ctr - sub_start < len_subpackets {
-                    let (subp, subplen) = parse(&bits[ctr..]);
-
-                    ctr += subplen;
-                    subpackets.push(subp);
+                let len_offset = reader.position();
+                let end = reader.read(15)? + reader.position();
+                while reader.position() < end {
+                    subpackets.push(parse_packet(reader)?);
+                }
+                if reader.position() != end {
+                    return Err(BitsError::LengthMismatch {
+                        offset: len_offset,
+                        expected: end,
+                        actual: reader.position(),
+                    });
                 }
+            }
 
-                subpackets
+            if !op.takes_operands(subpackets.len()) {
+                return Err(BitsError::OperandCount {
+                    offset: start,
+                    operator: op,
+                    count: subpackets.len(),
+                });
+            }
+            BITSPacket::Operator(op, subpackets)
+        }
+    };
+
+    Ok(BITSPacketVersioned { version, packet })
+}
+
+/// Read the groups of a literal, each a continuation bit followed by four bits of the value
+fn parse_literal(reader: &mut BitReader) -> Result<usize, BitsError> {
+    let mut value: usize = 0;
+    loop {
+        let offset = reader.position();
+        let group = reader.read(5)?;
+        if value.leading_zeros() < 4 {
+            return Err(BitsError::LiteralOverflow { offset });
+        }
+        value = (value << 4) | (group & 0xF);
+        if group & 0x10 == 0 {
+            return Ok(value);
+        }
+    }
+}
+
+/// Encode a packet as a hex transmission, padded to a whole number of bytes
+pub fn encode(packet: &BITSPacketVersioned, policy: LengthPolicy) -> Result<String, EncodeError> {
+    let mut writer = BitWriter::new();
+    encode_packet(&mut writer, packet, policy)?;
+    Ok(writer.into_hex())
+}
+
+/// Write a single packet, and all of its subpackets
+pub fn encode_packet(
+    writer: &mut BitWriter,
+    packet: &BITSPacketVersioned,
+    policy: LengthPolicy,
+) -> Result<(), EncodeError> {
+    if packet.version > 7 {
+        return Err(EncodeError::VersionTooLarge {
+            version: packet.version,
+        });
+    }
+    writer.write(packet.version as usize, 3);
+
+    match &packet.packet {
+        BITSPacket::Literal(value) => {
+            writer.write(LITERAL_TYPE, 3);
+            // Groups of four bits, with as few as possible but always at least one
+            let groups = ((usize::BITS - value.leading_zeros()) as usize)
+                .div_ceil(4)
+                .max(1);
+            for i in (0..groups).rev() {
+                writer.write_bool(i != 0);
+                writer.write(value >> (i * 4) & 0xF, 4);
+            }
+        }
+        BITSPacket::Operator(op, subs) => {
+            if !op.takes_operands(subs.len()) {
+                return Err(EncodeError::OperandCount {
+                    operator: *op,
+                    count: subs.len(),
+                });
+            }
+            writer.write(op.type_id(), 3);
+
+            let mut body = BitWriter::new();
+            for sub in subs {
+                encode_packet(&mut body, sub, policy)?;
+            }
+            let length = |length_type| match length_type {
+                LengthType::TotalBits => body.len(),
+                LengthType::SubpacketCount => subs.len(),
             };
+            let fits = |length_type: LengthType| length(length_type) >> length_type.width() == 0;
 
-            BITSPacket::Operator(op_type, subpackets)
+            let length_type = match policy {
+                LengthPolicy::Always(length_type) => Some(length_type).filter(|&t| fits(t)),
+                LengthPolicy::Shortest => [LengthType::SubpacketCount, LengthType::TotalBits]
+                    .into_iter()
+                    .find(|&t| fits(t)),
+            }
+            .ok_or(EncodeError::TooLong {
+                count: subs.len(),
+                bits: body.len(),
+            })?;
+
+            writer.write_bool(length_type == LengthType::SubpacketCount);
+            writer.write(length(length_type), length_type.width());
+            writer.append(&body);
         }
-    };
+    }
 
-    (
-        BITSPacketVersioned {
-            version,
-            packet: packet_type,
-        },
-        // Number of bits we've consumed
-        ctr,
-    )
+    Ok(())
 }
 
-impl BITSPacketVersioned {
-    pub fn eval(&self) -> usize {
+impl BITSOperator {
+    /// Apply the operator to the values of its subpackets, which there must be a valid number of
+    pub fn apply(&self, mut operands: impl Iterator<Item = usize>) -> usize {
         use BITSOperator::*;
-        use BITSPacket::*;
 
+        let mut pair = || (operands.next().unwrap(), operands.next().unwrap());
+        match self {
+            Sum => operands.sum(),
+            Product => operands.product(),
+            Minimum => operands.min().unwrap(),
+            Maximum => operands.max().unwrap(),
+            Greater => {
+                let (a, b) = pair();
+                (a > b) as usize
+            }
+            Less => {
+                let (a, b) = pair();
+                (a < b) as usize
+            }
+            Equal => {
+                let (a, b) = pair();
+                (a == b) as usize
+            }
+        }
+    }
+}
+
+impl BITSPacketVersioned {
+    pub fn eval(&self) -> usize {
         match &self.packet {
-            Literal(l) => *l,
-            Operator(Sum, subs) => subs.iter().map(Self::eval).sum(),
-            Operator(Product, subs) => subs.iter().map(Self::eval).product(),
-            Operator(Minimum, subs) => subs.iter().map(Self::eval).min().unwrap(),
-            Operator(Maximum, subs) => subs.iter().map(Self::eval).max().unwrap(),
-            Operator(Greater, subs) => (subs[0].eval() > subs[1].eval()) as usize,
-            Operator(Less, subs) => (subs[0].eval() < subs[1].eval()) as usize,
-            Operator(Equal, subs) => (subs[0].eval() == subs[1].eval()) as usize,
+            BITSPacket::Literal(l) => *l,
+            BITSPacket::Operator(op, subs) => op.apply(subs.iter().map(Self::eval)),
         }
     }
 }
@@ -190,24 +294,24 @@ impl BITSPacketVersioned {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
 
     #[test]
     fn example_literal() {
         let example_input = "D2FE28";
-        let bits = get_bits(example_input);
 
         let expected = BITSPacketVersioned {
             version: 6,
             packet: BITSPacket::Literal(2021),
         };
 
-        assert_eq!(parse(&bits).0, expected);
+        assert_eq!(parse(example_input), Ok(expected));
     }
 
     #[test]
     fn example_operator() {
         let example_input = "38006F45291200";
-        let bits = get_bits(example_input);
 
         let expected = BITSPacketVersioned {
             version: 1,
@@ -226,6 +330,200 @@ mod tests {
             ),
         };
 
-        assert_eq!(parse(&bits).0, expected);
+        assert_eq!(parse(example_input), Ok(expected));
+    }
+
+    #[test]
+    fn literal_overflow() {
+        // Version 0, literal with 17 groups of 1111
+        let mut bits = String::from("000100");
+        bits += &"11111".repeat(16);
+        bits += "01111";
+        let hex = to_hex(&bits);
+        assert_eq!(
+            parse(&hex),
+            Err(BitsError::LiteralOverflow { offset: 6 + 16 * 5 })
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        // Cut off in the middle of the second literal's first group, which starts at bit 39
+        assert_eq!(
+            parse("38006F4529"),
+            Err(BitsError::UnexpectedEnd {
+                offset: 39,
+                wanted: 5
+            })
+        );
+    }
+
+    #[test]
+    fn length_mismatch() {
+        // The example operator, with its subpacket length shortened from 27 to 26
+        let hex = to_hex(&format!(
+            "0011100{:015b}{}",
+            26, "1101000101001010010001001000000000"
+        ));
+        assert_eq!(
+            parse(&hex),
+            Err(BitsError::LengthMismatch {
+                offset: 7,
+                expected: 48,
+                actual: 49
+            })
+        );
+    }
+
+    #[test]
+    fn operand_count() {
+        // A less than operator with a single literal
+        let hex = to_hex(&format!("0011101{:011b}{}", 1, "11010001010"));
+        assert_eq!(
+            parse(&hex),
+            Err(BitsError::OperandCount {
+                offset: 0,
+                operator: BITSOperator::Less,
+                count: 1
+            })
+        );
+    }
+
+    fn literal(version: u8, value: usize) -> BITSPacketVersioned {
+        BITSPacketVersioned {
+            version,
+            packet: BITSPacket::Literal(value),
+        }
+    }
+
+    fn operator(
+        version: u8,
+        op: BITSOperator,
+        subs: Vec<BITSPacketVersioned>,
+    ) -> BITSPacketVersioned {
+        BITSPacketVersioned {
+            version,
+            packet: BITSPacket::Operator(op, subs),
+        }
+    }
+
+    #[test]
+    fn encode_examples() {
+        assert_eq!(
+            encode(&literal(6, 2021), LengthPolicy::Shortest),
+            Ok("D2FE28".to_string())
+        );
+        assert_eq!(
+            encode(
+                &operator(1, BITSOperator::Less, vec![literal(6, 10), literal(2, 20)]),
+                LengthPolicy::Always(LengthType::TotalBits)
+            ),
+            Ok("38006F45291200".to_string())
+        );
+        assert_eq!(
+            encode(
+                &operator(
+                    7,
+                    BITSOperator::Maximum,
+                    vec![literal(2, 1), literal(4, 2), literal(1, 3)]
+                ),
+                LengthPolicy::Shortest
+            ),
+            Ok("EE00D40C823060".to_string())
+        );
+    }
+
+    #[test]
+    fn encode_errors() {
+        assert_eq!(
+            encode(&literal(8, 0), LengthPolicy::Shortest),
+            Err(EncodeError::VersionTooLarge { version: 8 })
+        );
+        assert_eq!(
+            encode(
+                &operator(0, BITSOperator::Equal, vec![]),
+                LengthPolicy::Shortest
+            ),
+            Err(EncodeError::OperandCount {
+                operator: BITSOperator::Equal,
+                count: 0
+            })
+        );
+
+        // Too many subpackets to count, but few enough bits to give the total length
+        let wide = operator(0, BITSOperator::Sum, vec![literal(0, 1); 2048]);
+        assert_eq!(
+            encode(&wide, LengthPolicy::Always(LengthType::SubpacketCount)),
+            Err(EncodeError::TooLong {
+                count: 2048,
+                bits: 2048 * 11
+            })
+        );
+        let hex = encode(&wide, LengthPolicy::Shortest).unwrap();
+        assert_eq!(parse(&hex), Ok(wide));
+    }
+
+    #[test]
+    fn reencode_myinput() {
+        aoc_helpers::input::with_input(2021, 16, |input| {
+            let packet = parse(input).unwrap();
+            for length_type in [LengthType::TotalBits, LengthType::SubpacketCount] {
+                let hex = encode(&packet, LengthPolicy::Always(length_type)).unwrap();
+                assert_eq!(parse(&hex).as_ref(), Ok(&packet));
+            }
+        });
+    }
+
+    fn arb_packet() -> impl Strategy<Value = BITSPacketVersioned> {
+        use BITSOperator::*;
+
+        let leaf = (0..8_u8, any::<usize>()).prop_map(|(version, value)| literal(version, value));
+        leaf.prop_recursive(4, 64, 4, |inner| {
+            let op_and_subs = prop_oneof![
+                (
+                    prop_oneof![Just(Sum), Just(Product)],
+                    vec(inner.clone(), 0..4)
+                ),
+                (
+                    prop_oneof![Just(Minimum), Just(Maximum)],
+                    vec(inner.clone(), 1..4)
+                ),
+                (
+                    prop_oneof![Just(Greater), Just(Less), Just(Equal)],
+                    vec(inner, 2)
+                ),
+            ];
+            (0..8_u8, op_and_subs).prop_map(|(version, (op, subs))| operator(version, op, subs))
+        })
+    }
+
+    fn arb_policy() -> impl Strategy<Value = LengthPolicy> {
+        prop_oneof![
+            Just(LengthPolicy::Shortest),
+            Just(LengthPolicy::Always(LengthType::TotalBits)),
+            Just(LengthPolicy::Always(LengthType::SubpacketCount)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(packet in arb_packet(), policy in arb_policy()) {
+            let hex = encode(&packet, policy).unwrap();
+            prop_assert_eq!(hex.len() % 2, 0);
+            prop_assert_eq!(parse(&hex), Ok(packet));
+        }
+    }
+
+    /// Pad a string of binary digits to whole hex digits and convert it
+    fn to_hex(bits: &str) -> String {
+        let padded = format!("{:0<width$}", bits, width = bits.len().div_ceil(4) * 4);
+        padded
+            .as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let n = u32::from_str_radix(std::str::from_utf8(nibble).unwrap(), 2).unwrap();
+                std::char::from_digit(n, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect()
     }
 }
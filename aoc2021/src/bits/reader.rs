@@ -0,0 +1,109 @@
+use super::BitsError;
+
+/// Reads fields of any width straight out of a hex-encoded transmission, most significant bit
+/// first
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    hex: &'a [u8],
+    /// Offset of the next bit to be read
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(hex: &'a str) -> BitReader<'a> {
+        BitReader {
+            hex: hex.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    /// Offset of the next bit to be read, from the start of the transmission
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bits left to read
+    pub fn remaining(&self) -> usize {
+        self.hex.len() * 4 - self.pos
+    }
+
+    fn nibble(&self, index: usize) -> Result<usize, BitsError> {
+        let c = self.hex[index] as char;
+        c.to_digit(16)
+            .map(|d| d as usize)
+            .ok_or(BitsError::InvalidHex { index, found: c })
+    }
+
+    /// Read the next `width` bits as a number
+    ///
+    /// On error, nothing is consumed.
+    pub fn read(&mut self, width: usize) -> Result<usize, BitsError> {
+        assert!(width <= usize::BITS as usize, "field too wide");
+        if width > self.remaining() {
+            return Err(BitsError::UnexpectedEnd {
+                offset: self.pos,
+                wanted: width,
+            });
+        }
+
+        let mut value = 0;
+        let mut pos = self.pos;
+        let end = pos + width;
+        while pos < end {
+            // Take as much of the current hex digit as is needed
+            let available = 4 - pos % 4;
+            let take = available.min(end - pos);
+            let bits = (self.nibble(pos / 4)? >> (available - take)) & ((1 << take) - 1);
+            value = (value << take) | bits;
+            pos += take;
+        }
+
+        self.pos = end;
+        Ok(value)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, BitsError> {
+        self.read(1).map(|b| b == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unaligned_fields() {
+        // 1101 0010 1111 1110 0010 1000
+        let mut reader = BitReader::new("D2FE28");
+        assert_eq!(reader.read(3), Ok(6));
+        assert_eq!(reader.read(3), Ok(4));
+        assert_eq!(reader.read_bool(), Ok(true));
+        assert_eq!(reader.read(9), Ok(0b0_1111_1110));
+        assert_eq!(reader.position(), 16);
+        assert_eq!(reader.remaining(), 8);
+        assert_eq!(reader.read(0), Ok(0));
+    }
+
+    #[test]
+    fn errors() {
+        let mut reader = BitReader::new("F");
+        assert_eq!(
+            reader.read(5),
+            Err(BitsError::UnexpectedEnd {
+                offset: 0,
+                wanted: 5
+            })
+        );
+        assert_eq!(reader.read(4), Ok(15));
+
+        let mut reader = BitReader::new("1G");
+        assert_eq!(
+            reader.read(8),
+            Err(BitsError::InvalidHex {
+                index: 1,
+                found: 'G'
+            })
+        );
+        assert_eq!(reader.position(), 0);
+    }
+}
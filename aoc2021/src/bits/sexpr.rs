@@ -0,0 +1,268 @@
+//! Writing packets as S-expressions like `(sum (product 6 9) 3)`, and reading them back
+//!
+//! Versions aren't part of the notation: they're left out when writing, and read back as 0.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{BITSOperator, BITSPacket, BITSPacketVersioned};
+
+impl BITSOperator {
+    /// Name of the operator in S-expressions
+    pub fn name(&self) -> &'static str {
+        use BITSOperator::*;
+
+        match self {
+            Sum => "sum",
+            Product => "product",
+            Minimum => "min",
+            Maximum => "max",
+            Greater => "gt",
+            Less => "lt",
+            Equal => "eq",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BITSOperator> {
+        Self::ALL.into_iter().find(|op| op.name() == name)
+    }
+}
+
+impl fmt::Display for BITSPacketVersioned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.packet {
+            BITSPacket::Literal(value) => write!(f, "{}", value),
+            BITSPacket::Operator(op, subs) => {
+                write!(f, "({}", op.name())?;
+                for sub in subs {
+                    write!(f, " {}", sub)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Problems reading an S-expression, where `offset` is the byte offset into the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SexprError {
+    UnexpectedEnd,
+    UnexpectedChar {
+        offset: usize,
+        found: char,
+    },
+    UnknownOperator {
+        offset: usize,
+        name: String,
+    },
+    InvalidLiteral {
+        offset: usize,
+        text: String,
+    },
+    /// An operator has the wrong number of operands, e.g. `(gt 1)`
+    OperandCount {
+        offset: usize,
+        operator: BITSOperator,
+        count: usize,
+    },
+    /// There's more after the end of the expression
+    Trailing {
+        offset: usize,
+    },
+}
+
+impl fmt::Display for SexprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "Expression ended early"),
+            Self::UnexpectedChar { offset, found } => {
+                write!(f, "Unexpected {:?} at offset {}", found, offset)
+            }
+            Self::UnknownOperator { offset, name } => {
+                write!(f, "Unknown operator {:?} at offset {}", name, offset)
+            }
+            Self::InvalidLiteral { offset, text } => {
+                write!(f, "Invalid literal {:?} at offset {}", text, offset)
+            }
+            Self::OperandCount {
+                offset,
+                operator,
+                count,
+            } => write!(
+                f,
+                "Operator {:?} can't take {} operands at offset {}",
+                operator.name(),
+                count,
+                offset
+            ),
+            Self::Trailing { offset } => {
+                write!(
+                    f,
+                    "Unexpected text after the expression at offset {}",
+                    offset
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SexprError {}
+
+struct SexprParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> SexprParser<'a> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Take everything up to the next bracket or whitespace
+    fn atom(&mut self) -> &'a str {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn expr(&mut self) -> Result<BITSPacketVersioned, SexprError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let packet = match self.peek().ok_or(SexprError::UnexpectedEnd)? {
+            '(' => {
+                self.pos += 1;
+                self.skip_whitespace();
+                let name_start = self.pos;
+                let name = self.atom();
+                let op =
+                    BITSOperator::from_name(name).ok_or_else(|| SexprError::UnknownOperator {
+                        offset: name_start,
+                        name: name.to_string(),
+                    })?;
+
+                let mut subs = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek().ok_or(SexprError::UnexpectedEnd)? {
+                        ')' => break,
+                        _ => subs.push(self.expr()?),
+                    }
+                }
+                self.pos += 1;
+                if !op.takes_operands(subs.len()) {
+                    return Err(SexprError::OperandCount {
+                        offset: start,
+                        operator: op,
+                        count: subs.len(),
+                    });
+                }
+                BITSPacket::Operator(op, subs)
+            }
+            ')' => {
+                return Err(SexprError::UnexpectedChar {
+                    offset: start,
+                    found: ')',
+                })
+            }
+            _ => {
+                let text = self.atom();
+                BITSPacket::Literal(text.parse().map_err(|_| SexprError::InvalidLiteral {
+                    offset: start,
+                    text: text.to_string(),
+                })?)
+            }
+        };
+
+        Ok(BITSPacketVersioned { version: 0, packet })
+    }
+}
+
+impl FromStr for BITSPacketVersioned {
+    type Err = SexprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = SexprParser { text: s, pos: 0 };
+        let packet = parser.expr()?;
+        parser.skip_whitespace();
+        if parser.pos < s.len() {
+            return Err(SexprError::Trailing { offset: parser.pos });
+        }
+        Ok(packet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::parse;
+
+    #[test]
+    fn display() {
+        let packet = parse("9C0141080250320F1802104A08").unwrap();
+        assert_eq!(packet.to_string(), "(eq (sum 1 3) (product 2 2))");
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "(sum (product 6 9) 3 (min 7) (max 8 2) (gt 1 2) (lt 3 4) (eq 5 5))";
+        let packet: BITSPacketVersioned = text.parse().unwrap();
+        assert_eq!(packet.to_string(), text);
+        assert_eq!(packet.eval(), 54 + 3 + 7 + 8 + 1 + 1);
+
+        let spaced: BITSPacketVersioned = "  ( sum\n 1\t(product 2 3 ) ) ".parse().unwrap();
+        assert_eq!(spaced.to_string(), "(sum 1 (product 2 3))");
+    }
+
+    #[test]
+    fn errors() {
+        let err = |s: &str| s.parse::<BITSPacketVersioned>().unwrap_err();
+        assert_eq!(err("(sum 1 2"), SexprError::UnexpectedEnd);
+        assert_eq!(err(""), SexprError::UnexpectedEnd);
+        assert_eq!(
+            err(") 1"),
+            SexprError::UnexpectedChar {
+                offset: 0,
+                found: ')'
+            }
+        );
+        assert_eq!(
+            err("(sum (mean 1 2))"),
+            SexprError::UnknownOperator {
+                offset: 6,
+                name: "mean".to_string()
+            }
+        );
+        assert_eq!(
+            err("(sum 1 -2)"),
+            SexprError::InvalidLiteral {
+                offset: 7,
+                text: "-2".to_string()
+            }
+        );
+        assert_eq!(
+            err("(gt 1)"),
+            SexprError::OperandCount {
+                offset: 0,
+                operator: BITSOperator::Greater,
+                count: 1
+            }
+        );
+        assert_eq!(
+            err("(sum (min))"),
+            SexprError::OperandCount {
+                offset: 5,
+                operator: BITSOperator::Minimum,
+                count: 0
+            }
+        );
+        assert_eq!(err("(sum 1) 2"), SexprError::Trailing { offset: 8 });
+    }
+}
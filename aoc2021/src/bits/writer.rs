@@ -0,0 +1,108 @@
+/// Builds up a transmission a field at a time, most significant bit first, for output as hex
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    nibbles: Vec<u8>,
+    /// Number of bits written
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> BitWriter {
+        BitWriter::default()
+    }
+
+    /// Number of bits written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn push_bit(&mut self, bit: bool) {
+        if self.len.is_multiple_of(4) {
+            self.nibbles.push(0);
+        }
+        if bit {
+            *self.nibbles.last_mut().unwrap() |= 1 << (3 - self.len % 4);
+        }
+        self.len += 1;
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.nibbles[index / 4] >> (3 - index % 4) & 1 == 1
+    }
+
+    /// Write `value` as a field `width` bits wide
+    ///
+    /// Panics if the value doesn't fit.
+    pub fn write(&mut self, value: usize, width: usize) {
+        assert!(
+            width >= usize::BITS as usize || value >> width == 0,
+            "{} doesn't fit in {} bits",
+            value,
+            width
+        );
+        for i in (0..width).rev() {
+            self.push_bit(i < usize::BITS as usize && value >> i & 1 == 1);
+        }
+    }
+
+    pub fn write_bool(&mut self, bit: bool) {
+        self.push_bit(bit);
+    }
+
+    /// Write everything that's been written to `other`
+    pub fn append(&mut self, other: &BitWriter) {
+        for i in 0..other.len {
+            self.push_bit(other.bit(i));
+        }
+    }
+
+    /// Render as hex, padded with zeros to a whole number of bytes
+    pub fn into_hex(mut self) -> String {
+        while !self.len.is_multiple_of(8) {
+            self.push_bit(false);
+        }
+        self.nibbles
+            .iter()
+            .map(|&n| {
+                std::char::from_digit(n as u32, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::BitReader;
+
+    #[test]
+    fn round_trip() {
+        let mut writer = BitWriter::new();
+        writer.write(6, 3);
+        writer.write(4, 3);
+        writer.write_bool(true);
+        let mut rest = BitWriter::new();
+        rest.write(0b0_1111_1110, 9);
+        rest.write(0b00101, 5);
+        writer.append(&rest);
+        assert_eq!(writer.len(), 21);
+
+        let hex = writer.into_hex();
+        assert_eq!(hex, "D2FE28");
+        let mut reader = BitReader::new(&hex);
+        assert_eq!(reader.read(3), Ok(6));
+        assert_eq!(reader.read(18), Ok(0b10_0101_1111_1100_0101));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_wide() {
+        BitWriter::new().write(8, 3);
+    }
+}
@@ -35,13 +35,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(1);
-        assert_eq!(solve_part1(&parse_input(&input)), 1696);
+        crate::check_answer(1, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(1);
-        assert_eq!(solve_part2(&parse_input(&input)), 1737);
+        crate::check_answer(1, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -91,13 +91,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(10);
-        assert_eq!(solve_part1(&_input), 315693);
+        crate::check_answer(10, 1, solve_part1);
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(10);
-        assert_eq!(solve_part2(&_input), 1870887234);
+        crate::check_answer(10, 2, solve_part2);
     }
 }
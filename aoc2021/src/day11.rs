@@ -1,12 +1,11 @@
-use crate::field2d::{array_field::ArrayField, Field2D, Idx};
+use aoc_helpers::grids::{Dense, Diagonal, Grid};
+use aoc_helpers::UPoint;
 
-const WIDTH: usize = 10;
-const HEIGHT: usize = 10;
 const NUM_STEPS: usize = 100;
 
-type Field = ArrayField<u8, WIDTH, HEIGHT>;
+type Field = Dense<u8>;
 
-fn try_flash(idx: Idx, field: &mut Field) -> usize {
+fn try_flash(idx: UPoint, field: &mut Field) -> usize {
     let mut flashes = 0;
 
     if field[idx] > 9 {
@@ -15,8 +14,8 @@ fn try_flash(idx: Idx, field: &mut Field) -> usize {
         flashes += 1;
 
         // Increment and check adjacents
-        // NOTE: flatten instead of filter_map is a perf regression
-        for adj in field.adjacents(idx).into_iter().filter_map(|i| i) {
+        let adjacents: Vec<_> = field.adj_coords::<Diagonal>(idx).collect();
+        for adj in adjacents {
             // Don't inc things that have already flashed
             if field[adj] != 0 {
                 field[adj] += 1;
@@ -30,20 +29,7 @@ fn try_flash(idx: Idx, field: &mut Field) -> usize {
 
 #[aoc_generator(day11)]
 pub fn parse_input(input: &str) -> Field {
-    Field {
-        field: input
-            .lines()
-            .map(|l| {
-                l.bytes()
-                    .map(|n| n - 48)
-                    .collect::<Vec<_>>()
-                    .try_into()
-                    .unwrap()
-            })
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap(),
-    }
+    Dense::from_rows(input.lines().map(|l| l.bytes().map(|n| n - b'0')))
 }
 
 #[aoc(day11, part1)]
@@ -53,16 +39,10 @@ pub fn solve_part1(input: &Field) -> usize {
 
     for _ in 1..=NUM_STEPS {
         // Increment all octopi
-        field
-            .field
-            .iter_mut()
-            .flat_map(|l| l.iter_mut())
-            .for_each(|n| *n += 1);
-
-        for x in 0..WIDTH {
-            for y in 0..HEIGHT {
-                flashes += try_flash((x, y), &mut field);
-            }
+        field.elems_mut().iter_mut().for_each(|n| *n += 1);
+
+        for point in field.points().collect::<Vec<_>>() {
+            flashes += try_flash(point, &mut field);
         }
     }
 
@@ -75,25 +55,14 @@ pub fn solve_part2(input: &Field) -> usize {
     let mut day = 0;
 
     // Apparently this is faster than a hashmap
-    while !field
-        .field
-        .iter()
-        .flat_map(|c| c.iter())
-        .all(|n| n == &0_u8)
-    {
+    while !field.elems().iter().all(|n| n == &0_u8) {
         day += 1;
 
         // Increment all octopi
-        field
-            .field
-            .iter_mut()
-            .flat_map(|l| l.iter_mut())
-            .for_each(|n| *n += 1);
-
-        for x in 0..WIDTH {
-            for y in 0..HEIGHT {
-                try_flash((x, y), &mut field);
-            }
+        field.elems_mut().iter_mut().for_each(|n| *n += 1);
+
+        for point in field.points().collect::<Vec<_>>() {
+            try_flash(point, &mut field);
         }
     }
 
@@ -127,13 +96,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(11);
-        assert_eq!(solve_part1(&parse_input(&_input)), 1659);
+        crate::check_answer(11, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(11);
-        assert_eq!(solve_part2(&parse_input(&_input)), 227);
+        crate::check_answer(11, 2, |input| solve_part2(&parse_input(input)));
     }
 }
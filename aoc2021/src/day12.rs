@@ -183,13 +183,11 @@ start-RW";
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(12);
-        assert_eq!(solve_part1(&parse_input(&_input)), 3463);
+        crate::check_answer(12, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(12);
-        assert_eq!(solve_part2(&parse_input(&_input)), 91533);
+        crate::check_answer(12, 2, |input| solve_part2(&parse_input(input)));
     }
 }
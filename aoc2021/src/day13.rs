@@ -125,8 +125,7 @@ fold along x=5";
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(13);
-        assert_eq!(solve_part1(&parse_input(&_input)), 753);
+        crate::check_answer(13, 1, |input| solve_part1(&parse_input(input)));
     }
 
     // NOTE: no part 2 tests for this because I can't be bothered
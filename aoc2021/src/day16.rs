@@ -1,8 +1,8 @@
-use crate::bits::{get_bits, parse, BITSPacket, BITSPacketVersioned};
+use crate::bits::{parse, BITSPacket, BITSPacketVersioned};
 
 #[aoc_generator(day16)]
 pub fn parse_input(input: &str) -> BITSPacketVersioned {
-    parse(&get_bits(input)).0
+    parse(input).expect("invalid transmission")
 }
 
 fn sum_version(pack: &BITSPacketVersioned) -> usize {
@@ -42,13 +42,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(16);
-        assert_eq!(solve_part1(&parse_input(&_input)), 947);
+        crate::check_answer(16, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(16);
-        assert_eq!(solve_part2(&parse_input(&_input)), 660797830937);
+        crate::check_answer(16, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -63,24 +63,24 @@ mod tests {
     const EXAMPLE_INPUT: &'static str = "target area: x=20..30, y=-10..-5";
 
     #[test]
+    #[ignore = "part 1 isn't solved yet"]
     fn part1_example() {
         assert_eq!(solve_part1(&parse_input(&EXAMPLE_INPUT)), 45);
     }
 
     #[test]
+    #[ignore = "part 2 isn't solved yet"]
     fn part2_example() {
-        assert_eq!(solve_part2(&parse_input(&EXAMPLE_INPUT)), todo!());
+        assert_eq!(solve_part2(&parse_input(&EXAMPLE_INPUT)), 112);
     }
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(17);
-        assert_eq!(solve_part1(&parse_input(&_input)), todo!());
+        crate::check_answer(17, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(17);
-        assert_eq!(solve_part2(&parse_input(&_input)), todo!());
+        crate::check_answer(17, 2, |input| solve_part2(&parse_input(input)));
     }
 }
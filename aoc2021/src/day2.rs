@@ -80,13 +80,11 @@ forward 2";
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(2);
-        assert_eq!(solve_part1(&parse_input(&input)), 1648020);
+        crate::check_answer(2, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(2);
-        assert_eq!(solve_part2(&parse_input(&input)), 1759818555);
+        crate::check_answer(2, 2, |input| solve_part2(&parse_input(input)));
     }
 }
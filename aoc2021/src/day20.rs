@@ -1,20 +1,18 @@
-use crate::field2d::{compressed_field::CompressedField, Field2D};
+use aoc_helpers::grids::{Diagonal, Grid, Sparse};
+
+type Image = Sparse<bool>;
 
 #[aoc_generator(day20)]
-pub fn parse_input(input: &str) -> (String, CompressedField<bool>) {
+pub fn parse_input(input: &str) -> (String, Image) {
     let mut split = input.split("\n\n");
     let algo = split.next().unwrap().to_string();
 
     let image_raw = split.next().unwrap();
-    let image_axis = image_raw.lines().count();
-    let final_size = (image_axis + 2).pow(2);
-    let mut image = CompressedField::new(vec![false; final_size], image_axis);
-
-    for (y, line) in image_raw.lines().enumerate() {
-        for (x, byte) in line.bytes().enumerate() {
-            if byte == b'#' {
-                image[(x + 2, y + 2)] = true;
-            }
+    let mut image = Sparse::new(false);
+
+    for (y, line) in (0..).zip(image_raw.lines()) {
+        for (x, byte) in (0..).zip(line.bytes()) {
+            image.insert((x, y), byte == b'#');
         }
     }
 
@@ -22,23 +20,21 @@ pub fn parse_input(input: &str) -> (String, CompressedField<bool>) {
 }
 
 #[aoc(day20, part1)]
-pub fn solve_part1((algo, image): &(String, CompressedField<bool>)) -> usize {
+pub fn solve_part1((algo, image): &(String, Image)) -> usize {
     let image = image.clone();
 
-    for search_ext in 1..=2 {
-        for y in search_ext..(image.height() + search_ext) {
-            for x in search_ext..(image.width() + search_ext) {
-                let mut algo_index = 0;
-                let adjacents = image.adjacents((x, y));
-            }
+    for _search_ext in 1..=2 {
+        for point in image.points() {
+            let mut algo_index = 0;
+            let adjacents = image.adj_coords::<Diagonal>(point);
         }
     }
 
-    image.iter().filter(|p| p == &&true).count()
+    image.iter_set().filter(|(_, &p)| p).count()
 }
 
 #[aoc(day20, part2)]
-pub fn solve_part2((algo, image): &(String, CompressedField<bool>)) -> usize {
+pub fn solve_part2((algo, image): &(String, Image)) -> usize {
     unimplemented!()
 }
 
@@ -56,24 +52,24 @@ mod tests {
 ..###";
 
     #[test]
+    #[ignore = "part 1 isn't solved yet"]
     fn part1_example() {
         assert_eq!(solve_part1(&parse_input(&EXAMPLE_INPUT)), 35);
     }
 
     #[test]
+    #[ignore = "part 2 isn't solved yet"]
     fn part2_example() {
-        assert_eq!(solve_part2(&parse_input(&EXAMPLE_INPUT)), unimplemented!());
+        assert_eq!(solve_part2(&parse_input(&EXAMPLE_INPUT)), 3351);
     }
 
     #[test]
     fn part1_myinput() {
-        let _input = crate::get_input_for_day(20);
-        assert_eq!(solve_part1(&parse_input(&_input)), unimplemented!());
+        crate::check_answer(20, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(20);
-        assert_eq!(solve_part2(&parse_input(&_input)), unimplemented!());
+        crate::check_answer(20, 2, |input| solve_part2(&parse_input(input)));
     }
 }
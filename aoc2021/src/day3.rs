@@ -98,13 +98,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(3);
-        assert_eq!(solve_part1(&input), 2261546);
+        crate::check_answer(3, 1, solve_part1);
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(3);
-        assert_eq!(solve_part2(&input), 6775520);
+        crate::check_answer(3, 2, solve_part2);
     }
 }
@@ -243,13 +243,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(4);
-        assert_eq!(solve_part1(&parse_input(&input)), 25410);
+        crate::check_answer(4, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(4);
-        assert_eq!(solve_part2(&parse_input(&input)), 2730);
+        crate::check_answer(4, 2, |input| solve_part2(&parse_input(input)));
     }
 }
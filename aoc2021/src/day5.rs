@@ -1,4 +1,4 @@
-use crate::field2d::compressed_field::CompressedField;
+use aoc_helpers::grids::Dense;
 use std::cmp::Ordering::*;
 
 type Line = [[usize; 2]; 2];
@@ -24,9 +24,9 @@ pub fn parse_input(input: &str) -> Vec<Line> {
 #[aoc(day5, part1)]
 pub fn solve_part1(input: &[Line]) -> usize {
     let size_limit = input.iter().flatten().flatten().max().unwrap() + 1;
-    let mut field = CompressedField::new(vec![0_usize; size_limit.pow(2)], size_limit);
+    let mut field = Dense::new(size_limit, size_limit, 0_usize);
 
-    for line in input{
+    for line in input {
         let (x0, x1) = (line[0][0], line[1][0]);
         let (y0, y1) = (line[0][1], line[1][1]);
 
@@ -41,15 +41,15 @@ pub fn solve_part1(input: &[Line]) -> usize {
         }
     }
 
-    field.field.iter().filter(|c| c > &&1).count()
+    field.elems().iter().filter(|c| c > &&1).count()
 }
 
 #[aoc(day5, part2)]
 pub fn solve_part2(input: &[Line]) -> usize {
     let size_limit = input.iter().flatten().flatten().max().unwrap() + 1;
-    let mut field = CompressedField::new(vec![0_usize; size_limit * size_limit], size_limit);
+    let mut field = Dense::new(size_limit, size_limit, 0_usize);
 
-    for line in input{
+    for line in input {
         let (x0, x1) = (line[0][0], line[1][0]);
         let (y0, y1) = (line[0][1], line[1][1]);
 
@@ -82,7 +82,7 @@ pub fn solve_part2(input: &[Line]) -> usize {
         }
     }
 
-    field.field.iter().filter(|c| c > &&1).count()
+    field.elems().iter().filter(|c| c > &&1).count()
 }
 
 #[cfg(test)]
@@ -131,13 +131,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(5);
-        assert_eq!(solve_part1(&parse_input(&input)), 5169);
+        crate::check_answer(5, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(5);
-        assert_eq!(solve_part2(&parse_input(&input)), 22083);
+        crate::check_answer(5, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -62,13 +62,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(6);
-        assert_eq!(solve_part1(&parse_input(&input)), 389726);
+        crate::check_answer(6, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(6);
-        assert_eq!(solve_part2(&parse_input(&input)), 1743335992042);
+        crate::check_answer(6, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -66,13 +66,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(7);
-        assert_eq!(solve_part1(&parse_input(&input)), 336040);
+        crate::check_answer(7, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(7);
-        assert_eq!(solve_part2(&parse_input(&input)), 94813675);
+        crate::check_answer(7, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -189,14 +189,12 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(8);
-        assert_eq!(solve_part1(&parse_input(&input)), 247);
+        crate::check_answer(8, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let input = crate::get_input_for_day(8);
-        assert_eq!(solve_part2(&parse_input(&input)), 933305);
+        crate::check_answer(8, 2, |input| solve_part2(&parse_input(input)));
     }
 
     #[test]
@@ -1,94 +1,46 @@
-use crate::field2d::{compressed_field::CompressedField, Field2D};
-use hashbrown::HashSet;
+use aoc_helpers::grids::{self, Dense, Grid, Orthogonal};
 
 const NUM_LARGEST: usize = 3;
-
-pub fn low_points(field: &CompressedField<usize>) -> Vec<(usize, usize)> {
-    let mut low_points = Vec::with_capacity(field.height());
-
-    for y in 0..field.height() {
-        for x in 0..field.width() {
-            let current = field[(x, y)];
-            let is_low_point = field
-                .adjacents((x, y))
-                .iter()
-                .step_by(2)
-                // Replacing this with flatten() is a perf regression
-                .filter_map(|p| *p)
-                .all(|point| field[point] > current);
-
-            if is_low_point {
-                low_points.push((x, y));
-            }
-        }
-    }
-
-    low_points
+const BASIN_EDGE: u8 = 9;
+
+pub fn low_points(field: &Dense<u8>) -> Vec<(usize, usize)> {
+    field
+        .iter_all()
+        .filter(|&(p, &current)| {
+            field
+                .neighbours::<Orthogonal>(p)
+                .all(|(_, &adj)| adj > current)
+        })
+        .map(|(p, _)| p)
+        .collect()
 }
 
 #[aoc_generator(day9)]
-pub fn parse_input(input: &str) -> CompressedField<usize> {
-    let row_len = input.lines().next().unwrap().len();
-
-    CompressedField::new(
-        input
-            .lines()
-            .flat_map(str::bytes)
-            .map(|p| p as usize - 48)
-            .collect(),
-        row_len,
-    )
+pub fn parse_input(input: &str) -> Dense<u8> {
+    grids::parse(input, b"", |b| b.is_ascii_digit().then(|| b - b'0'))
+        .unwrap()
+        .grid
 }
 
 #[aoc(day9, part1)]
-pub fn solve_part1(input: &CompressedField<usize>) -> usize {
+pub fn solve_part1(input: &Dense<u8>) -> usize {
     let low_points = low_points(input);
-    low_points.iter().map(|i| input[*i]).sum::<usize>() + low_points.len()
+    low_points.iter().map(|&i| input[i] as usize).sum::<usize>() + low_points.len()
 }
 
 #[aoc(day9, part2)]
-pub fn solve_part2(input: &CompressedField<usize>) -> usize {
-    let mut largest_sizes = [0_usize; NUM_LARGEST];
-
-    for (lx, ly) in low_points(input) {
-        // Note: Use the field width as a capacity so it scales correctly
-        // Stores the final basin
-        let mut basin = HashSet::with_capacity(input.width());
-        // Stores the uninspected edges
-        let mut basin_edge = Vec::with_capacity(input.width());
-        basin.insert((lx, ly));
-        basin_edge.push((lx, ly));
-
-        // Look through the outer edge of the basin
-        while let Some((x, y)) = basin_edge.pop() {
-            let current_val = input[(x, y)];
-
-            // Iterate through the squares adjacent to it
-            for adj in input.adjacents((x, y)).into_iter().step_by(2).flatten() {
-                let adj_val = input[adj];
-                // If:
-                // - it's not 9
-                // - it's higher than the current square
-                // - we haven't already recorded it
-                if adj_val != 9 && current_val < adj_val && !basin.contains(&adj) {
-                    basin.insert(adj);
-                    basin_edge.push(adj);
-                }
-            }
-        }
-
-        // Add it to the array of largest sizes if it's larger than any of them
-        largest_sizes.sort_unstable();
-        for n in largest_sizes.iter_mut() {
-            if &basin.len() > n {
-                *n = basin.len();
-                break;
-            } else {
-            }
-        }
-    }
-
-    largest_sizes.into_iter().product()
+pub fn solve_part2(input: &Dense<u8>) -> usize {
+    // Everything that isn't an edge is part of some basin
+    let mut sizes: Vec<_> =
+        grids::regions::<Orthogonal, _>(input, |&l, &r| (l == BASIN_EDGE) == (r == BASIN_EDGE))
+            .regions
+            .into_iter()
+            .filter(|basin| input[basin.points[0]] != BASIN_EDGE)
+            .map(|basin| basin.area())
+            .collect();
+
+    sizes.sort_unstable_by(|l, r| r.cmp(l));
+    sizes.into_iter().take(NUM_LARGEST).product()
 }
 
 #[cfg(test)]
@@ -113,13 +65,11 @@ mod tests {
 
     #[test]
     fn part1_myinput() {
-        let input = crate::get_input_for_day(9);
-        assert_eq!(solve_part1(&parse_input(&input)), 475);
+        crate::check_answer(9, 1, |input| solve_part1(&parse_input(input)));
     }
 
     #[test]
     fn part2_myinput() {
-        let _input = crate::get_input_for_day(9);
-        assert_eq!(solve_part2(&parse_input(&_input)), 1092012);
+        crate::check_answer(9, 2, |input| solve_part2(&parse_input(input)));
     }
 }
@@ -20,14 +20,11 @@ pub mod day8;
 pub mod day9;
 
 pub mod bits;
-pub mod field2d;
 
+/// Check a solution against my recorded answer, see [`aoc_helpers::answers::assert_answer`]
 #[cfg(test)]
-fn get_input_for_day(day: usize) -> String {
-    std::fs::read_to_string(format!("input/2021/day{}.txt", day))
-        .unwrap()
-        .trim()
-        .to_string()
+fn check_answer<T: std::fmt::Display>(day: u32, part: u32, solve: impl FnOnce(&str) -> T) {
+    aoc_helpers::answers::assert_answer(2021, day, part, solve);
 }
 
 aoc_lib! {year = 2021}
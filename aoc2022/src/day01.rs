@@ -74,7 +74,7 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&crate::get_input(1)), 73_211);
+        crate::check_answer(1, 1, solve_part1);
     }
 
     #[test]
@@ -84,6 +84,6 @@ mod tests {
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&crate::get_input(1)), 213_958);
+        crate::check_answer(1, 2, solve_part2);
     }
 }
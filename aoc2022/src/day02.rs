@@ -96,7 +96,7 @@ C Z";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&crate::get_input(2)), 10816);
+        crate::check_answer(2, 1, solve_part1);
     }
 
     #[test]
@@ -106,6 +106,6 @@ C Z";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&crate::get_input(2)), 11657);
+        crate::check_answer(2, 2, solve_part2);
     }
 }
@@ -102,7 +102,7 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(3))), 7568);
+        crate::check_answer(3, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -112,6 +112,6 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(3))), 2780);
+        crate::check_answer(3, 2, |input| solve_part2(&generate(input)));
     }
 }
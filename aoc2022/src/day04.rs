@@ -52,7 +52,7 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(4))), 475);
+        crate::check_answer(4, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -62,6 +62,6 @@ mod tests {
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(4))), 825);
+        crate::check_answer(4, 2, |input| solve_part2(&generate(input)));
     }
 }
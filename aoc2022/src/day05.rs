@@ -153,7 +153,7 @@ move 1 from 1 to 2";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(&solve_part1(&generate(&crate::get_input(5))), "BZLVHBWQF");
+        crate::check_answer(5, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -163,6 +163,6 @@ move 1 from 1 to 2";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(&solve_part2(&generate(&crate::get_input(5))), "TDGJQTZSL");
+        crate::check_answer(5, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -22,7 +22,6 @@ fn solve_part2(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::get_input;
     const SAMPLE_INPUT: &[(&str, usize, usize)] = &[
         ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
         ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
@@ -41,7 +40,7 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&get_input(6)), 1760);
+        crate::check_answer(6, 1, solve_part1);
     }
 
     #[test]
@@ -176,7 +176,7 @@ $ ls
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(7))), 1444896);
+        crate::check_answer(7, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -186,6 +186,6 @@ $ ls
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(7))), 404395);
+        crate::check_answer(7, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -117,7 +117,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(8))), 1546);
+            crate::check_answer(8, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -138,7 +138,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(8))), 519064);
+            crate::check_answer(8, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
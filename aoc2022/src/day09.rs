@@ -131,7 +131,7 @@ R 2";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(09))), 5960);
+        crate::check_answer(9, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -158,6 +158,6 @@ U 20"
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(09))), 2327);
+        crate::check_answer(9, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -265,7 +265,7 @@ noop";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(10))), 12460);
+        crate::check_answer(10, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -284,15 +284,6 @@ noop";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(
-            solve_part2(&generate(&crate::get_input(10))),
-            "\n####.####.####.###..###...##..#..#.#....
-#.......#.#....#..#.#..#.#..#.#.#..#....
-###....#..###..#..#.#..#.#..#.##...#....
-#.....#...#....###..###..####.#.#..#....
-#....#....#....#....#.#..#..#.#.#..#....
-####.####.#....#....#..#.#..#.#..#.####."
-                .to_owned()
-        );
+        crate::check_answer(10, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -1,3 +1,5 @@
+use aoc_helpers::numtheory;
+
 type Worry = u128;
 
 #[derive(Debug, Copy, Clone)]
@@ -54,22 +56,11 @@ impl Operation {
             Self::Sub(o) => *worry -= o.get_value(*worry),
         }
     }
-
-    fn try_simplify(lhs: Self, rhs: Self) -> Option<Self> {
-        use Operand::Literal;
-        use Operation::*;
-
-        match (lhs, rhs) {
-            (Mul(Literal(x)), Div(Literal(y))) if x % y == 0 => Some(Mul(Literal(x / y))),
-
-            _ => None,
-        }
-    }
 }
 
 #[derive(Debug, Clone)]
 struct Monkey {
-    holding: Vec<(Worry, Vec<Operation>)>,
+    holding: Vec<Worry>,
     op: Operation,
     test_mod: Worry,
     on_success: usize,
@@ -84,7 +75,7 @@ impl From<&str> for Monkey {
         Monkey {
             holding: lines.next().unwrap()[18..]
                 .split(", ")
-                .map(|i| (i.parse().unwrap(), vec![]))
+                .map(|i| i.parse().unwrap())
                 .collect(),
             op: lines.next().unwrap()[23..].into(),
             test_mod: lines.next().unwrap()[21..].parse().unwrap(),
@@ -108,15 +99,15 @@ fn solve_part1(input: &[Monkey]) -> usize {
 
     for _round in 0..PART1_ROUNDS {
         for m in 0..monkeys.len() {
-            for (mut item, _) in monkeys[m].holding.drain(..).collect::<Vec<_>>() {
+            for mut item in monkeys[m].holding.drain(..).collect::<Vec<_>>() {
                 monkeys[m].op.apply(&mut item);
                 item /= 3;
                 if item % monkeys[m].test_mod == 0 {
                     let on_success = monkeys[m].on_success;
-                    monkeys[on_success].holding.push((item, vec![]));
+                    monkeys[on_success].holding.push(item);
                 } else {
                     let on_fail = monkeys[m].on_fail;
-                    monkeys[on_fail].holding.push((item, vec![]));
+                    monkeys[on_fail].holding.push(item);
                 }
                 monkeys[m].inspections += 1;
             }
@@ -132,23 +123,27 @@ const PART2_ROUNDS: usize = 10_000;
 
 #[aoc(day11, part2)]
 fn solve_part2(input: &[Monkey]) -> u128 {
-    // TODO: chinese remainder theorem
     let mut monkeys = input.to_owned();
 
+    // Every test only cares about worry modulo its own `test_mod`, so worry can be kept modulo
+    // all of them at once without changing where any item goes
+    let modulus = numtheory::lcm_all(monkeys.iter().map(|m| m.test_mod))
+        .expect("Test divisors overflowed when combined");
+
     for _round in 0..PART2_ROUNDS {
         for m in 0..monkeys.len() {
-            for (item, mut ops) in monkeys[m].holding.clone() {
-                ops.push(monkeys[m].op);
+            for mut item in monkeys[m].holding.drain(..).collect::<Vec<_>>() {
+                monkeys[m].op.apply(&mut item);
+                item %= modulus;
                 if item % monkeys[m].test_mod == 0 {
                     let on_success = monkeys[m].on_success;
-                    monkeys[on_success].holding.push((item, ops));
+                    monkeys[on_success].holding.push(item);
                 } else {
                     let on_fail = monkeys[m].on_fail;
-                    monkeys[on_fail].holding.push((item, ops));
+                    monkeys[on_fail].holding.push(item);
                 }
                 monkeys[m].inspections += 1;
             }
-            monkeys[m].holding.clear();
         }
     }
 
@@ -197,18 +192,16 @@ Monkey 3:
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(11))), 67830);
+        crate::check_answer(11, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
-    #[ignore]
     fn part2_example() {
         assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 2713310158);
     }
 
     #[test]
-    #[ignore]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(11))), todo!());
+        crate::check_answer(11, 2, |input| solve_part2(&generate(input)));
     }
 }
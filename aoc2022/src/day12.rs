@@ -1,58 +1,37 @@
-use aoc_helpers::{Adjacents, UPoint as Point};
-use ndarray::Array2;
-use pathfinding::prelude::dijkstra;
+use aoc_helpers::{
+    grids::{self, Dense, Grid, Orthogonal},
+    search::{self, grid_steps, Path},
+    UPoint as Point,
+};
 
 #[derive(Clone, Debug)]
 struct Field {
-    map: Array2<u8>,
+    map: Dense<u8>,
     start: Point,
     end: Point,
 }
 
 impl Field {
     fn new(input: &str) -> Self {
-        let height = input.lines().count();
-        let width = input.lines().next().unwrap().len();
-
-        let mut to_return = Self {
-            map: Array2::zeros((height, width)),
-            start: (0, 0),
-            end: (0, 0),
-        };
-
-        for (i, line) in input.lines().enumerate() {
-            for (j, c) in line.bytes().enumerate() {
-                to_return.map[(i, j)] = match c {
-                    b'S' => {
-                        to_return.start = (i, j);
-                        b'a'
-                    }
-                    b'E' => {
-                        to_return.end = (i, j);
-                        b'z'
-                    }
-                    _ => c,
-                } - b'a';
-            }
+        let parsed = grids::parse(input, b"SE", |c| match c {
+            b'S' => Some(0),
+            b'E' => Some(b'z' - b'a'),
+            b'a'..=b'z' => Some(c - b'a'),
+            _ => None,
+        })
+        .expect("invalid heightmap");
+
+        Self {
+            start: parsed.single(b'S').expect("no unique start found"),
+            end: parsed.single(b'E').expect("no unique end found"),
+            map: parsed.grid,
         }
-        to_return
-    }
-
-    fn valid_moves(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
-        Adjacents::<4>::new(pos)
-            .constrain(self.map.dim())
-            .filter(move |i| self.map[*i].saturating_sub(self.map[pos]) <= 1)
     }
 
-    fn path_start_end(&self) -> Option<(Vec<Point>, usize)> {
-        self.path_from(self.start)
-    }
-
-    fn path_from(&self, start: Point) -> Option<(Vec<Point>, usize)> {
-        dijkstra(
-            &start,
-            // We don't care about the weighting so it's always 1
-            |p| self.valid_moves(*p).map(|p| (p, 1)),
+    fn shortest_path(&self, starts: impl IntoIterator<Item = Point>) -> Option<Path<Point, usize>> {
+        search::bfs(
+            starts,
+            grid_steps::<Orthogonal, _>(&self.map, |&from, &to| to.saturating_sub(from) <= 1),
             |p| p == &self.end,
         )
     }
@@ -65,20 +44,22 @@ fn generate(input: &str) -> Field {
 
 #[aoc(day12, part1)]
 fn solve_part1(field: &Field) -> usize {
-    field.path_start_end().unwrap().1
+    field.shortest_path([field.start]).unwrap().cost
 }
 
 #[aoc(day12, part2)]
 fn solve_part2(field: &Field) -> usize {
+    // Search from all the a's at once
     field
-        .map
-        .indexed_iter()
-        // Find all the a's
-        .filter_map(|(i, &e)| if e == 0 { Some(i) } else { None })
-        // Ignore anything that doesn't find a path
-        .filter_map(|i| field.path_from(i).map(|(_, n)| n))
-        .min()
+        .shortest_path(
+            field
+                .map
+                .iter_all()
+                .filter(|(_, &e)| e == 0)
+                .map(|(p, _)| p),
+        )
         .unwrap()
+        .cost
 }
 
 #[cfg(test)]
@@ -104,7 +85,7 @@ abdefghi";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(12))), 517);
+        crate::check_answer(12, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -114,6 +95,6 @@ abdefghi";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(12))), 512);
+        crate::check_answer(12, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -135,7 +135,7 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(13))), 5760);
+        crate::check_answer(13, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -145,6 +145,6 @@ mod tests {
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(13))), 26670);
+        crate::check_answer(13, 2, |input| solve_part2(&generate(input)));
     }
 }
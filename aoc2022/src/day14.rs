@@ -1,8 +1,10 @@
 use itertools::Itertools;
-use ndarray::s;
 use std::fmt::Display;
 
-use aoc_helpers::{grids::Offset, UPoint as Point};
+use aoc_helpers::{
+    grids::{Grid, Offset},
+    IPoint as Point,
+};
 
 type Cave = Offset<Tile>;
 
@@ -133,11 +135,13 @@ const FLOOR_OFFSET: usize = 2;
 #[aoc(day14, part2)]
 fn solve_part2(cave: &Cave) -> usize {
     let mut cave = cave.clone();
-    let side_expand = cave.true_dim().1;
+    let side_expand = cave.height();
     cave.expand(0, FLOOR_OFFSET, side_expand, side_expand, Tile::Air);
 
-    let floor_height = cave.limits.1 .1;
-    cave.grid.slice_mut(s![.., floor_height]).fill(Tile::Rock);
+    let ((x0, _), (x1, floor_height)) = cave.limits();
+    for x in x0..=x1 {
+        cave[(x, floor_height)] = Tile::Rock;
+    }
 
     let mut particles = 0;
     loop {
@@ -158,8 +162,8 @@ fn solve_part2(cave: &Cave) -> usize {
                     "Particle #{} has fallen into the void from {:?}, limits {:?} {:?}",
                     particles + 1,
                     current,
-                    cave.limits.0,
-                    cave.limits.1,
+                    cave.limits().0,
+                    cave.limits().1,
                 );
             }
             SandFall::Falls(_) => unreachable!(),
@@ -187,7 +191,7 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(14))), 994);
+        crate::check_answer(14, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -197,6 +201,6 @@ mod tests {
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(14))), 26283);
+        crate::check_answer(14, 2, |input| solve_part2(&generate(input)));
     }
 }
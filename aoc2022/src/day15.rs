@@ -1,4 +1,10 @@
-use aoc_helpers::{manhattan, ranges::Range, Pair, UPoint as Point};
+use std::collections::HashSet;
+
+use aoc_helpers::{
+    manhattan,
+    ranges::{Range, RangeSet},
+    Pair, UPoint as Point,
+};
 
 mod parse {
     use aoc_helpers::{parse::*, IPoint};
@@ -86,11 +92,9 @@ fn generate(input: &str) -> (Vec<SensPair>, Pair<usize>) {
     )
 }
 
-const MERGE_PASSES: usize = 3;
-
 fn part1_inner((pairs, (_, y_off)): &(Vec<SensPair>, Point), goal: usize) -> usize {
     let goal_line = goal + y_off;
-    let mut intersects = Vec::new();
+    let mut covered = RangeSet::new();
 
     for &(sensor, beacon) in pairs.iter() {
         // Maximum distance the sensor can see
@@ -107,50 +111,20 @@ fn part1_inner((pairs, (_, y_off)): &(Vec<SensPair>, Point), goal: usize) -> usi
         // between the sensor diamond and the goal line
         let reach = max_dist - goal_dist;
 
-        let pair = Range {
+        covered.insert(Range {
             start: sensor.0 - reach,
             end: sensor.0 + reach,
-        };
-        let mut combined = false;
-
-        for i in 0..intersects.len() {
-            if let Some(new_pair) = pair.union(intersects[i]) {
-                intersects.remove(i);
-                intersects.push(new_pair);
-                combined = true;
-                break;
-            }
-        }
-
-        if !combined {
-            intersects.push(pair);
-        }
+        });
     }
 
-    for _ in 0..MERGE_PASSES {
-        for pairi in 1..intersects.len() {
-            // Since we're shrinking intersects, we need to ensure we don't
-            // overrun it
-            if pairi == intersects.len() {
-                break;
-            }
-
-            let (&[.., lpair], remaining) = intersects.split_at(pairi)
-            else { unreachable!() };
-
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..remaining.len() {
-                if let Some(new_pair) = lpair.union(remaining[i]) {
-                    intersects.remove(pairi - 1);
-                    intersects.remove(i + pairi - 1);
-                    intersects.push(new_pair);
-                    break;
-                }
-            }
-        }
-    }
+    // Beacons sitting on the goal line are, of course, places where a beacon can be
+    let beacons: HashSet<_> = pairs
+        .iter()
+        .filter(|(_, (_, by))| *by == goal_line)
+        .map(|&(_, (bx, _))| bx)
+        .collect();
 
-    intersects.into_iter().map(|r| r.len() - 1).sum()
+    covered.len() - beacons.into_iter().filter(|&b| covered.contains(b)).count()
 }
 
 const GOAL_LINE: usize = 2_000_000;
@@ -188,8 +162,8 @@ fn part2_inner(
             }
         });
 
-        if let Some(x) = xs.demolish(intersects) {
-            return (x.to_single().unwrap() - x_off) * 4_000_000 + (y - y_off);
+        if let Some(x) = xs.demolish(intersects).min() {
+            return (x - x_off) * 4_000_000 + (y - y_off);
         }
     }
 
@@ -228,7 +202,7 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(15))), 4879972);
+        crate::check_answer(15, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
@@ -238,9 +212,6 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
     #[test]
     fn part2_mine() {
-        assert_eq!(
-            solve_part2(&generate(&crate::get_input(15))),
-            12525726647448
-        );
+        crate::check_answer(15, 2, |input| solve_part2(&generate(input)));
     }
 }
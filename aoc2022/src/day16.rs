@@ -79,7 +79,6 @@ fn solve_part2(input: &ValveNetwork) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
@@ -94,22 +93,24 @@ Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
 
     #[test]
+    #[ignore = "part 1 isn't solved yet"]
     fn part1_example() {
         assert_eq!(solve_part1(&generate(SAMPLE_INPUT)), 1651);
     }
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(16))), todo!());
+        crate::check_answer(16, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
+    #[ignore = "part 2 isn't solved yet"]
     fn part2_example() {
-        assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+        assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 1707);
     }
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(16))), todo!());
+        crate::check_answer(16, 2, |input| solve_part2(&generate(input)));
     }
 }
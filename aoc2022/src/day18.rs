@@ -43,7 +43,6 @@ fn solve_part2(_input: &Drop) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "2,2,2
@@ -64,13 +63,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 1 isn't solved yet"]
         fn example() {
             assert_eq!(solve_part1(&generate(SAMPLE_INPUT)), 64);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(18))), todo!());
+            crate::check_answer(18, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -78,13 +78,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 2 isn't solved yet"]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 58);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(18))), todo!());
+            crate::check_answer(18, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
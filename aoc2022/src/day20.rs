@@ -66,7 +66,6 @@ fn solve_part2(input: &[(usize, isize)]) -> isize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "1
@@ -84,16 +83,17 @@ mod tests {
 
     #[test]
     fn part1_mine() {
-        assert_eq!(solve_part1(&generate(&crate::get_input(20))), todo!());
+        crate::check_answer(20, 1, |input| solve_part1(&generate(input)));
     }
 
     #[test]
+    #[ignore = "part 2 isn't solved yet"]
     fn part2_example() {
-        assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+        assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 1_623_178_306);
     }
 
     #[test]
     fn part2_mine() {
-        assert_eq!(solve_part2(&generate(&crate::get_input(20))), todo!());
+        crate::check_answer(20, 2, |input| solve_part2(&generate(input)));
     }
 }
@@ -1,7 +1,6 @@
 use std::collections::BTreeMap;
-use std::ops::{Add, Div, Mul, Sub};
 
-use aoc_helpers::arith::{FullOp, Op};
+use aoc_helpers::arith::{Expr, Op, Rational};
 
 mod parse {
     use super::*;
@@ -33,7 +32,7 @@ mod parse {
 
     fn job(input: &str) -> IResult<Job> {
         alt((
-            map(usize, |n| Job::Literal(Fixed(n))),
+            map(usize, Job::Literal),
             map(
                 tuple((name, delimited(tag(" "), op, tag(" ")), name)),
                 |(l, op, r)| Job::Op(op, l, r),
@@ -50,138 +49,36 @@ mod parse {
     }
 }
 
-/// A simple mathematical expression
-///
-/// It can either be a known value, which is just mutated as other known-value operations are
-/// applied, or it can be unknown, which will hold the operations applied to it for later
-/// evaluation.
-#[derive(Clone, Debug, PartialEq)]
-enum Expr {
-    Var(Vec<FullOp<usize>>),
-    Fixed(usize),
-}
-
-use Expr::*;
-
-impl Expr {
-    /// Tries to mathematically simplify the expression by combining similar operations or pruning
-    /// no-ops
-    fn simplify(&mut self) {
-        // We can only simplify Exprs with variables in them
-        if let Var(ref mut ops) = self {
-            let mut i = 0;
-
-            // TODO: this would probably be faster backwards...
-            while i < ops.len() - 1 {
-                if let Some(newop) = ops[i].try_combine(ops[i + 1]) {
-                    ops.remove(i);
-                    ops.remove(i);
-                    if !newop.is_noop() {
-                        ops.insert(i, newop);
-                    }
-                }
-
-                // Remove noops
-                if ops[i].is_noop() {
-                    ops.remove(i);
-                } else {
-                    i += 1;
-                }
-            }
-        }
-    }
-
-    fn push_op(&mut self, op: FullOp<usize>) {
-        if let Var(ref mut list) = self {
-            list.push(op);
-            self.simplify();
-        } else {
-            panic!("called push_op on a known Expr");
-        }
-    }
-}
-
-impl Add<Self> for Expr {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (mut v @ Var(_), Fixed(n)) | (Fixed(n), mut v @ Var(_)) => {
-                v.push_op(FullOp(Op::Add, n));
-                v
-            }
-            (Fixed(l), Fixed(r)) => Fixed(l + r),
-            (Var(_), Var(_)) => unreachable!("Tried to perform an add on two variable expressions"),
-        }
-    }
-}
-
-impl Sub<Self> for Expr {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (mut v @ Var(_), Fixed(n)) | (Fixed(n), mut v @ Var(_)) => {
-                v.push_op(FullOp(Op::Sub, n));
-                v
-            }
-            (Fixed(l), Fixed(r)) => Fixed(l - r),
-            (Var(_), Var(_)) => panic!("Tried to perform a sub on two unknown expressions"),
-        }
-    }
-}
-
-impl Mul<Self> for Expr {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (mut v @ Var(_), Fixed(n)) | (Fixed(n), mut v @ Var(_)) => {
-                v.push_op(FullOp(Op::Mul, n));
-                v
-            }
-            (Fixed(l), Fixed(r)) => Fixed(l * r),
-            _ => panic!("Tried to perform a mul on two unknown expressions"),
-        }
-    }
-}
-
-impl Div<Self> for Expr {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (mut v @ Var(_), Fixed(n)) | (Fixed(n), mut v @ Var(_)) => {
-                v.push_op(FullOp(Op::Div, n));
-                v
-            }
-            (Fixed(l), Fixed(r)) => Fixed(l / r),
-            _ => panic!("Tried to perform a div on two unknown expressions"),
-        }
-    }
-}
-
 type Name = [u8; 4];
 
+const ROOT: Name = *b"root";
+const HUMN: Name = *b"humn";
+
 /// A job held by a monkey
 #[derive(Debug, Clone, PartialEq)]
 enum Job {
-    Literal(Expr),
+    Literal(usize),
     Op(Op, Name, Name),
 }
 
-impl Job {
-    fn resolve(&self, map: &BTreeMap<Name, Job>) -> Expr {
-        match self {
-            Job::Literal(n) => n.clone(),
-            Job::Op(op, l, r) => {
-                // TODO: cloning here could be a potential slowdown, but it
-                // seems impossible to avoid
-                // Perhaps each monkey is only used once, so we can pull out of the map?
-                let l = map[l].clone();
-                let r = map[r].clone();
-                op.apply(l.resolve(map), r.resolve(map))
-            }
-        }
+/// Build the expression tree for what monkey `name` will yell
+///
+/// If `unknown` is given, that monkey's job is replaced by the unknown.
+fn expr(name: Name, map: &BTreeMap<Name, Job>, unknown: Option<Name>) -> Expr<Rational> {
+    if Some(name) == unknown {
+        return Expr::Var;
+    }
+    match &map[&name] {
+        &Job::Literal(n) => Expr::Const(Rational::from(n as i128)),
+        Job::Op(op, l, r) => Expr::binary(*op, expr(*l, map, unknown), expr(*r, map, unknown)),
     }
 }
 
+fn to_integer(n: &Rational) -> usize {
+    assert!(n.is_integer(), "{n} isn't a whole number");
+    usize::try_from(n.to_integer()).expect("answer should be non-negative")
+}
+
 #[aoc_generator(day21)]
 fn generate(input: &str) -> BTreeMap<Name, Job> {
     let monkeys = parse::monkeys(input).unwrap().1;
@@ -195,35 +92,20 @@ fn generate(input: &str) -> BTreeMap<Name, Job> {
 
 #[aoc(day21, part1)]
 fn solve_part1(input: &BTreeMap<Name, Job>) -> usize {
-    if let Fixed(n) = input[b"root"].resolve(input) {
-        n
-    } else {
-        panic!("part 1 somehow has a variable expression in it")
-    }
+    let root = expr(ROOT, input, None)
+        .value()
+        .expect("part 1 has no unknowns");
+    to_integer(&root)
 }
 
 #[aoc(day21, part2)]
 fn solve_part2(input: &BTreeMap<Name, Job>) -> usize {
-    let mut input = input.clone();
-    input.insert(*b"humn", Job::Literal(Var(Vec::with_capacity(input.len()))));
-
-    let (mut current, to_solve) = {
-        let Job::Op(_, l, r) = input[b"root"] else {
-            unreachable!()
-        };
-        let root_solutions = (input[&l].resolve(&input), input[&r].resolve(&input));
-        match root_solutions {
-            (Fixed(n), Var(l)) | (Var(l), Fixed(n)) => (n, l),
-            _ => unreachable!(),
-        }
+    let Job::Op(_, l, r) = input[&ROOT] else {
+        panic!("root monkey should be comparing two others")
     };
-
-    println!("{current} from {to_solve:?}");
-    for op in to_solve.into_iter().rev() {
-        current = op.apply_rev(current);
-    }
-
-    current
+    let lhs = expr(l, input, Some(HUMN));
+    let rhs = expr(r, input, Some(HUMN));
+    to_integer(&lhs.solve(rhs).expect("couldn't solve for humn"))
 }
 
 #[cfg(test)]
@@ -257,10 +139,7 @@ hmdt: 32";
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part1(&generate(&crate::get_input(21))),
-                121868120894282
-            );
+            crate::check_answer(21, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -274,7 +153,7 @@ hmdt: 32";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(21))), todo!());
+            crate::check_answer(21, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
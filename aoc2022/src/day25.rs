@@ -191,10 +191,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part1(&generate(&crate::get_input(25))),
-                "2=222-2---22=1=--1-2".to_owned()
-            );
+            crate::check_answer(25, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -202,13 +199,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "day 25 has no part 2 puzzle, so there's no answer to check"]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 0);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(25))), todo!());
+            crate::check_answer(25, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
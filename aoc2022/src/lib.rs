@@ -32,10 +32,8 @@ mod day25;
 
 aoc_lib! { year = 2022 }
 
+/// Check a solution against my recorded answer, see [`aoc_helpers::answers::assert_answer`]
 #[cfg(test)]
-fn get_input(day: u32) -> String {
-    std::fs::read_to_string(format!("./input/2022/day{day}.txt"))
-        .unwrap()
-        .trim()
-        .to_owned()
+fn check_answer<T: std::fmt::Display>(day: u32, part: u32, solve: impl FnOnce(&str) -> T) {
+    aoc_helpers::answers::assert_answer(2022, day, part, solve);
 }
@@ -52,7 +52,6 @@ fn solve_part2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     mod part1 {
@@ -73,7 +72,7 @@ treb7uchet"
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(01)), 54630);
+            crate::check_answer(1, 1, solve_part1);
         }
     }
 
@@ -98,7 +97,7 @@ zoneight234
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(01)), 54770);
+            crate::check_answer(1, 2, solve_part2);
         }
     }
 }
@@ -57,7 +57,6 @@ fn solve_part2(input: &str) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
@@ -76,7 +75,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(02)), 2268);
+            crate::check_answer(2, 1, solve_part1);
         }
     }
 
@@ -90,7 +89,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(02)), 63542);
+            crate::check_answer(2, 2, solve_part2);
         }
     }
 }
@@ -127,7 +127,6 @@ fn solve_part2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "467..114..
@@ -201,7 +200,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(03)), 537832);
+            crate::check_answer(3, 1, solve_part1);
         }
     }
 
@@ -217,7 +216,7 @@ mod tests {
         #[test]
         #[ignore]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(03)), todo!());
+            crate::check_answer(3, 2, solve_part2);
         }
     }
 }
@@ -55,7 +55,6 @@ fn solve_part2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
@@ -75,7 +74,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(04)), 25010);
+            crate::check_answer(4, 1, solve_part1);
         }
     }
 
@@ -89,7 +88,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(04)), 9924412);
+            crate::check_answer(4, 2, solve_part2);
         }
     }
 }
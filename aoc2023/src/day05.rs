@@ -1,3 +1,5 @@
+use aoc_helpers::ranges::{Range, RangeSet};
+
 type MapRange = [usize; 3];
 
 fn parse_range(input: &str) -> MapRange {
@@ -40,7 +42,7 @@ impl<'a> Map<&'a str> {
 impl<K> Map<K> {
     fn lookup(&self, val: usize) -> usize {
         for &[ds, ss, len] in &self.ranges {
-            if (ss..=(ss + len)).contains(&val) {
+            if (ss..(ss + len)).contains(&val) {
                 #[allow(clippy::cast_possible_wrap)]
                 return val
                     .checked_add_signed(ds as isize - ss as isize)
@@ -49,6 +51,26 @@ impl<K> Map<K> {
         }
         val
     }
+
+    /// Map every value in `input` at once
+    ///
+    /// Input ranges which straddle the edge of a map range are split at the boundary.
+    fn transform(&self, input: &RangeSet) -> RangeSet {
+        let mut unmapped = input.clone();
+        let mut mapped = RangeSet::new();
+
+        for &[ds, ss, len] in self.ranges.iter().filter(|r| r[2] != 0) {
+            let source = Range::from_len(ss, len);
+            let hits = unmapped.intersection(&source.into());
+            mapped.extend(hits.iter().map(|Range { start, end }| Range {
+                start: start - ss + ds,
+                end: end - ss + ds,
+            }));
+            unmapped.remove(source);
+        }
+
+        mapped.union(&unmapped)
+    }
 }
 
 fn parse_seeds(line: &str) -> impl Iterator<Item = usize> + '_ {
@@ -90,6 +112,16 @@ fn follow_through(maps: &[Map<usize>], seed: usize) -> usize {
     cur
 }
 
+fn follow_through_ranges(maps: &[Map<usize>], seeds: &RangeSet) -> RangeSet {
+    let mut cur = maps[0].transform(seeds);
+    let mut dest = maps[0].dest;
+    for _ in 1..maps.len() {
+        cur = maps[dest].transform(&cur);
+        dest = maps[dest].dest;
+    }
+    cur
+}
+
 #[aoc(day05, part1)]
 fn solve_part1(input: &str) -> usize {
     let mut iter = input.split("\n\n");
@@ -106,20 +138,21 @@ fn solve_part1(input: &str) -> usize {
 fn solve_part2(input: &str) -> usize {
     let mut iter = input.split("\n\n");
     let seeds: Vec<_> = parse_seeds(iter.next().expect("empty input")).collect();
-    let seeds = seeds.chunks(2).map(|c| (c[0], c[1]));
+    let seeds: RangeSet = seeds
+        .chunks(2)
+        .filter(|c| c[1] != 0)
+        .map(|c| Range::from_len(c[0], c[1]))
+        .collect();
 
     let maps = parse_input(iter);
 
-    seeds
-        .flat_map(|(start, len)| start..=(start + len))
-        .map(|s| follow_through(&maps, s))
+    follow_through_ranges(&maps, &seeds)
         .min()
-        .expect("no maps in input")
+        .expect("no seeds in input")
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "seeds: 79 14 55 13
@@ -166,7 +199,7 @@ humidity-to-location map:
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(05)), 251346198);
+            crate::check_answer(5, 1, solve_part1);
         }
     }
 
@@ -179,9 +212,8 @@ humidity-to-location map:
         }
 
         #[test]
-        #[ignore]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(05)), todo!());
+            crate::check_answer(5, 2, solve_part2);
         }
     }
 }
@@ -123,7 +123,6 @@ fn solve_part2(input: &[(Hand, usize)]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -165,7 +164,7 @@ QQQJA 483";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(07))), 251545216);
+            crate::check_answer(7, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -179,7 +178,7 @@ QQQJA 483";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(07))), todo!());
+            crate::check_answer(7, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use aoc_helpers::numtheory;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Direction {
     Left,
@@ -87,28 +89,28 @@ fn find_last_letters(tree: &NodeTree, letter: u8) -> Vec<usize> {
 
 #[aoc(day08, part2)]
 fn solve_part2((dirs, tree, _, _): &(Vec<Direction>, NodeTree, usize, usize)) -> usize {
-    let mut cursors = find_last_letters(tree, b'A');
     let goals = find_last_letters(tree, b'Z');
 
-    for (n, di) in (0..dirs.len()).cycle().enumerate() {
-        println!("{cursors:?}");
-        for cur in &mut cursors {
-            *cur = match dirs.get(di) {
-                Some(Direction::Left) => tree[*cur].1,
-                Some(Direction::Right) => tree[*cur].2,
+    // Each ghost reaches its goal for the first time after exactly one trip around its loop, so
+    // they all line up at the lowest common multiple of those trip lengths
+    let trips = find_last_letters(tree, b'A').into_iter().map(|mut cursor| {
+        for (n, di) in (0..dirs.len()).cycle().enumerate() {
+            cursor = match dirs.get(di) {
+                Some(Direction::Left) => tree[cursor].1,
+                Some(Direction::Right) => tree[cursor].2,
                 _ => unreachable!(),
             };
+            if goals.contains(&cursor) {
+                return n + 1;
+            }
         }
-        if cursors.iter().all(|c| goals.contains(c)) {
-            return n + 1;
-        }
-    }
-    unreachable!()
+        unreachable!()
+    });
+    numtheory::lcm_all(trips).expect("Ghosts never line up")
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "LLR
@@ -127,7 +129,7 @@ ZZZ = (ZZZ, ZZZ)";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(08))), 14893);
+            crate::check_answer(8, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -140,9 +142,8 @@ ZZZ = (ZZZ, ZZZ)";
         }
 
         #[test]
-        #[ignore]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(08))), todo!());
+            crate::check_answer(8, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
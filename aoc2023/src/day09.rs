@@ -63,7 +63,6 @@ fn solve_part2(input: &[Vec<isize>]) -> isize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "0 3 6 9 12 15
@@ -80,7 +79,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(09))), 1934898178);
+            crate::check_answer(9, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -94,7 +93,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(09))), 1129);
+            crate::check_answer(9, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -57,7 +57,6 @@ fn solve_part2(input: &[(usize, usize)]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "...#......
@@ -81,7 +80,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(11))), 9418609);
+            crate::check_answer(11, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -92,7 +91,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(11))), 593821230983);
+            crate::check_answer(11, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -103,7 +103,6 @@ fn solve_part2(_input: &[Row]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -124,13 +123,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 1 isn't solved yet"]
         fn example() {
             assert_eq!(solve_part1(&generate(SAMPLE_INPUT)), 21);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(12))), todo!());
+            crate::check_answer(12, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -138,13 +138,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 2 isn't solved yet"]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 525_152);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(12))), todo!());
+            crate::check_answer(12, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
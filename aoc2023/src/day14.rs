@@ -1,3 +1,4 @@
+use aoc_helpers::cycles;
 use ndarray::prelude::*;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -5,6 +6,7 @@ use rayon::prelude::*;
 type Space = u8;
 const SLIDING: u8 = b'O';
 const STATIC: u8 = b'#';
+const EMPTY: u8 = b'.';
 
 #[aoc_generator(day14)]
 fn generate(input: &str) -> Array2<Space> {
@@ -59,14 +61,59 @@ fn solve_part1(input: &Array2<Space>) -> usize {
     .sum()
 }
 
+/// Roll every sliding rock as far as it will go along `axis`
+///
+/// Rocks roll towards index 0 if `backwards` is set, otherwise towards the far end.
+fn tilt(platform: &mut Array2<Space>, axis: Axis, backwards: bool) {
+    for mut lane in platform.lanes_mut(axis) {
+        let len = lane.len();
+        let index = |i: usize| if backwards { i } else { len - 1 - i };
+
+        // Where the next rock will come to rest, counted in the direction of the roll
+        let mut rest = 0;
+        for i in 0..len {
+            match lane[index(i)] {
+                STATIC => rest = i + 1,
+                SLIDING => {
+                    lane[index(i)] = EMPTY;
+                    lane[index(rest)] = SLIDING;
+                    rest += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Tilt the platform north, west, south and then east
+fn spin_cycle(platform: &Array2<Space>) -> Array2<Space> {
+    let mut platform = platform.clone();
+    tilt(&mut platform, Axis(0), true);
+    tilt(&mut platform, Axis(1), true);
+    tilt(&mut platform, Axis(0), false);
+    tilt(&mut platform, Axis(1), false);
+    platform
+}
+
+/// Total load on the north support beams
+fn north_load(platform: &Array2<Space>) -> usize {
+    let height = platform.nrows();
+    platform
+        .indexed_iter()
+        .filter(|(_, &s)| s == SLIDING)
+        .map(|((row, _), _)| height - row)
+        .sum()
+}
+
+const SPIN_CYCLES: usize = 1_000_000_000;
+
 #[aoc(day14, part2)]
-fn solve_part2(_input: &Array2<Space>) -> usize {
-    todo!()
+fn solve_part2(input: &Array2<Space>) -> usize {
+    cycles::value_after(input.clone(), spin_cycle, SPIN_CYCLES, north_load)
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "O....#....
@@ -90,7 +137,7 @@ O.#..O.#.#
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(14))), 107142);
+            crate::check_answer(14, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -99,12 +146,12 @@ O.#..O.#.#
 
         #[test]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 64);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(14))), todo!());
+            crate::check_answer(14, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
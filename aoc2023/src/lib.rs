@@ -27,12 +27,10 @@ fn manhattan_dist((x1, y1): (usize, usize), (x2, y2): (usize, usize)) -> usize {
     x1.abs_diff(x2) + y1.abs_diff(y2)
 }
 
+/// Check a solution against my recorded answer, see [`aoc_helpers::answers::assert_answer`]
 #[cfg(test)]
-fn get_input(day: u32) -> String {
-    std::fs::read_to_string(format!("./input/2023/day{day}.txt"))
-        .unwrap()
-        .trim()
-        .to_owned()
+fn check_answer<T: std::fmt::Display>(day: u32, part: u32, solve: impl FnOnce(&str) -> T) {
+    aoc_helpers::answers::assert_answer(2023, day, part, solve);
 }
 
 #[cfg(test)]
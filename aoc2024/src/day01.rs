@@ -63,7 +63,6 @@ fn solve_part2(input: &Generated) -> usize {
 
 #[cfg(test)]
 mod test {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -78,19 +77,23 @@ mod test {
         generate(SAMPLE_INPUT)
     }
 
-    fn mine_parsed() -> Generated {
-        generate(&crate::get_input(01))
-    }
-
     #[test_case(sample_parsed(), 11; "sample")]
-    #[test_case(mine_parsed(), 3714264; "mine")]
     fn part1(parsed: Generated, solution: usize) {
         assert_eq!(solve_part1(&parsed), solution);
     }
 
+    #[test]
+    fn part1_mine() {
+        crate::check_answer(1, 1, |input| solve_part1(&generate(input)));
+    }
+
     #[test_case(sample_parsed(), 31; "sample")]
-    #[test_case(mine_parsed(), 18805872; "mine")]
     fn part2(parsed: Generated, solution: usize) {
         assert_eq!(solve_part2(&parsed), solution);
     }
+
+    #[test]
+    fn part2_mine() {
+        crate::check_answer(1, 2, |input| solve_part2(&generate(input)));
+    }
 }
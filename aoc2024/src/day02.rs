@@ -72,7 +72,6 @@ fn solve_part2(input: &[Vec<usize>]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -101,7 +100,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(02))), 287);
+            crate::check_answer(2, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -115,7 +114,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(02))), todo!());
+            crate::check_answer(2, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
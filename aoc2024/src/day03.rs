@@ -70,7 +70,6 @@ fn solve_part2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT_PART1: &str =
@@ -86,7 +85,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&crate::get_input(03)), 169021493);
+            crate::check_answer(3, 1, solve_part1);
         }
     }
 
@@ -103,7 +102,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&crate::get_input(03)), 111762583);
+            crate::check_answer(3, 2, solve_part2);
         }
     }
 }
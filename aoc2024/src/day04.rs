@@ -136,7 +136,6 @@ fn solve_part2(input: &[Vec<u8>]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "MMMSXXMASM
@@ -160,7 +159,7 @@ MXMXAXMASX";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(04))), 2378);
+            crate::check_answer(4, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -174,7 +173,7 @@ MXMXAXMASX";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(04))), 1796);
+            crate::check_answer(4, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -169,7 +169,6 @@ fn solve_part2((fragments, updates): &(Vec<PageOrderFragment>, Vec<PageUpdate>))
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "47|53
@@ -211,7 +210,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(05))), 4905);
+            crate::check_answer(5, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -225,7 +224,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(05))), 6204);
+            crate::check_answer(5, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
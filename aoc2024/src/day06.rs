@@ -1,3 +1,4 @@
+use aoc_helpers::grids::{self, Grid};
 use std::cmp::{max_by_key, min_by_key};
 use std::collections::BTreeSet;
 
@@ -5,28 +6,22 @@ type Point = (usize, usize);
 
 #[aoc_generator(day06)]
 fn generate(input: &str) -> (Field, Point) {
-    let mut obstacles = BTreeSet::new();
-    let mut start = None;
-
-    for (y, line) in input.lines().rev().enumerate() {
-        for (x, ch) in line.bytes().enumerate() {
-            match ch {
-                b'#' => {
-                    obstacles.insert((x, y));
-                }
-                b'^' => start = Some((x, y)),
-                _ => {}
-            }
-        }
-    }
+    let parsed = grids::parse(input, b"#^", |b| {
+        matches!(b, b'.' | b'#' | b'^').then_some(())
+    })
+    .expect("invalid map");
+    let (width, height) = parsed.grid.dims();
+
+    // The map is stored with y increasing upwards
+    let flip = |(x, y): Point| (x, height - 1 - y);
 
     (
         Field {
-            obstacles,
-            height: input.lines().count(),
-            width: input.lines().next().unwrap().len(),
+            obstacles: parsed.all(b'#').iter().copied().map(flip).collect(),
+            height,
+            width,
         },
-        start.expect("no start found"),
+        flip(parsed.single(b'^').expect("no unique start found")),
     )
 }
 
@@ -127,7 +122,6 @@ fn solve_part2(_input: &(Field, Point)) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "....#.....
@@ -151,7 +145,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(06))), 5534);
+            crate::check_answer(6, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -159,13 +153,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 2 isn't solved yet"]
         fn example() {
             assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 6);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(06))), todo!());
+            crate::check_answer(6, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -97,7 +97,6 @@ fn solve_part2(input: &[(usize, Vec<usize>)]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "190: 10 19
@@ -120,7 +119,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(07))), 303766880536);
+            crate::check_answer(7, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -134,10 +133,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part2(&generate(&crate::get_input(07))),
-                337041851384440
-            );
+            crate::check_answer(7, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
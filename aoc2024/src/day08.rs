@@ -1,7 +1,10 @@
-use crate::grid::{Grid, Point};
+use aoc_helpers::{
+    grids::{Dense, Grid},
+    UPoint as Point,
+};
 use std::collections::{BTreeSet, HashMap};
 
-type Field = (HashMap<u8, Vec<Point>>, Grid<()>);
+type Field = (HashMap<u8, Vec<Point>>, Dense<()>);
 
 #[aoc_generator(day08)]
 fn generate(input: &str) -> Field {
@@ -13,33 +16,40 @@ fn generate(input: &str) -> Field {
                 continue;
             }
 
-            antennas
-                .entry(ch)
-                .or_insert_with(Vec::new)
-                .push((x, y).into());
+            antennas.entry(ch).or_insert_with(Vec::new).push((x, y));
         }
     }
 
     (
         antennas,
-        Grid::empty(
+        Dense::new(
             input.lines().next().unwrap().as_bytes().len(),
             input.lines().count(),
+            (),
         ),
     )
 }
 
+/// Calculate the offset from `from` to `to`.
+#[allow(clippy::cast_possible_wrap)]
+fn vector(from: Point, to: Point) -> (isize, isize) {
+    (
+        to.0 as isize - from.0 as isize,
+        to.1 as isize - from.1 as isize,
+    )
+}
+
 fn antinodes_p1((antennas, grid): &Field) -> BTreeSet<Point> {
     let mut nodes = BTreeSet::new();
 
     for antennas in antennas.values() {
         for (i, &first) in antennas.iter().enumerate() {
             for &second in antennas.iter().skip(i + 1) {
-                let (xdiff, ydiff) = second.vector(first);
-                if let Some(first_antinode) = grid.offset_point(first, (xdiff, ydiff)) {
+                let (xdiff, ydiff) = vector(second, first);
+                if let Some(first_antinode) = grid.offset(first, (xdiff, ydiff)) {
                     nodes.insert(first_antinode);
                 }
-                if let Some(second_antinode) = grid.offset_point(second, (-xdiff, -ydiff)) {
+                if let Some(second_antinode) = grid.offset(second, (-xdiff, -ydiff)) {
                     nodes.insert(second_antinode);
                 }
             }
@@ -60,7 +70,7 @@ fn antinodes_p2((antennas, grid): &Field) -> BTreeSet<Point> {
     for antennas in antennas.values() {
         for (i, &first) in antennas.iter().enumerate() {
             for &second in antennas.iter().skip(i + 1) {
-                let (xdiff, ydiff) = Point::vector(second, first);
+                let (xdiff, ydiff) = vector(second, first);
                 for node in grid
                     .raycast(first, (-xdiff, -ydiff))
                     .chain(grid.raycast(second, (xdiff, ydiff)))
@@ -81,7 +91,6 @@ fn solve_part2(input: &Field) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "............
@@ -114,8 +123,8 @@ mod tests {
 ..........";
             let field = generate(partial);
             let antinodes = antinodes_p1(&field);
-            assert!(antinodes.contains(&(6, 2).into()));
-            assert!(antinodes.contains(&(3, 8).into()));
+            assert!(antinodes.contains(&(6, 2)));
+            assert!(antinodes.contains(&(3, 8)));
             assert_eq!(antinodes.len(), 2);
         }
 
@@ -126,7 +135,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(08))), 332);
+            crate::check_answer(8, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -140,7 +149,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(08))), 1174);
+            crate::check_answer(8, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -138,7 +138,6 @@ fn solve_part2(_input: &Disk) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -176,16 +175,26 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
-        #[test_case(SAMPLE_INPUT)]
-        #[test_case(crate::get_input(09).as_bytes(); "mine")]
-        #[test_case(b"12345")]
-        fn compressed_length(disk_raw: &[u8]) {
+        fn assert_compressed_length(disk_raw: &[u8]) {
             let disk = generate(disk_raw);
             let disk_files_len: usize = disk.files.iter().map(|&len| len as usize).sum();
             let compressed_len = Compress::disk(&disk).count();
             assert_eq!(disk_files_len, compressed_len);
         }
 
+        #[test_case(SAMPLE_INPUT)]
+        #[test_case(b"12345")]
+        fn compressed_length(disk_raw: &[u8]) {
+            assert_compressed_length(disk_raw);
+        }
+
+        #[test]
+        fn compressed_length_mine() {
+            aoc_helpers::input::with_input(2024, 9, |input| {
+                assert_compressed_length(input.as_bytes());
+            });
+        }
+
         #[test]
         fn example() {
             assert_eq!(solve_part1(&generate(SAMPLE_INPUT)), 1928);
@@ -193,10 +202,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part1(&generate(&crate::get_input(09).as_bytes())),
-                6399153661894
-            );
+            crate::check_answer(9, 1, |input| solve_part1(&generate(input.as_bytes())));
         }
     }
 
@@ -204,16 +210,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 2 isn't solved yet"]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 2858);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part2(&generate(&crate::get_input(09).as_bytes())),
-                todo!()
-            );
+            crate::check_answer(9, 2, |input| solve_part2(&generate(input.as_bytes())));
         }
     }
 }
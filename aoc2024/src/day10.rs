@@ -1,17 +1,17 @@
 use std::collections::BTreeSet;
 
-use crate::grid::Point;
+use aoc_helpers::{
+    grids::{self, Dense, Grid, Orthogonal},
+    UPoint as Point,
+};
 
-type TopoMap = crate::grid::Grid<u8, crate::grid::Orthogonal>;
+type TopoMap = Dense<u8>;
 
 #[aoc_generator(day10)]
 fn generate(input: &str) -> TopoMap {
-    TopoMap::new(
-        input
-            .lines()
-            .map(|line| line.bytes().map(|b| b - b'0').collect())
-            .collect(),
-    )
+    grids::parse(input, b"", |b| b.is_ascii_digit().then(|| b - b'0'))
+        .expect("invalid topographic map")
+        .grid
 }
 
 fn get_score_p1(map: &TopoMap, trailhead: Point, visited: &mut BTreeSet<Point>) -> usize {
@@ -24,7 +24,7 @@ fn get_score_p1(map: &TopoMap, trailhead: Point, visited: &mut BTreeSet<Point>)
     }
 
     // recursive case(s)
-    map.neighbours(trailhead)
+    map.neighbours::<Orthogonal>(trailhead)
         .filter(|(_, &h)| (h == t_height + 1))
         .map(|(p, _)| get_score_p1(map, p, visited))
         .sum()
@@ -48,7 +48,7 @@ fn get_score_p2(map: &TopoMap, trailhead: Point) -> usize {
     }
 
     // recursive case(s)
-    map.neighbours(trailhead)
+    map.neighbours::<Orthogonal>(trailhead)
         .filter(|(_, &h)| (h == t_height + 1))
         .map(|(p, _)| get_score_p2(map, p))
         .sum()
@@ -65,7 +65,6 @@ fn solve_part2(input: &TopoMap) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "89010123
@@ -87,7 +86,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(10))), 841);
+            crate::check_answer(10, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -101,7 +100,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(10))), 1875);
+            crate::check_answer(10, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -39,7 +39,6 @@ fn solve_part2(_input: &[usize]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
     use test_case::test_case;
 
@@ -55,7 +54,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(11))), 213625);
+            crate::check_answer(11, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -63,13 +62,14 @@ mod tests {
         use super::*;
 
         #[test]
+        #[ignore = "part 2 isn't solved yet"]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 65_601_038_650_482);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(11))), todo!());
+            crate::check_answer(11, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -1,23 +1,32 @@
-type Garden = crate::grid::Grid<u8>;
+use aoc_helpers::grids::{self, Dense, Orthogonal};
+
+type Garden = Dense<u8>;
 
 #[aoc_generator(day12)]
 fn generate(input: &str) -> Garden {
-    Garden::new(input.lines().map(|line| line.bytes().collect()).collect())
+    Garden::from_rows(input.lines().map(str::bytes))
 }
 
 #[aoc(day12, part1)]
-fn solve_part1(_input: &Garden) -> usize {
-    todo!()
+fn solve_part1(input: &Garden) -> usize {
+    grids::regions::<Orthogonal, _>(input, u8::eq)
+        .regions
+        .iter()
+        .map(|plot| plot.area() * plot.perimeter)
+        .sum()
 }
 
 #[aoc(day12, part2)]
-fn solve_part2(_input: &Garden) -> usize {
-    todo!()
+fn solve_part2(input: &Garden) -> usize {
+    grids::regions::<Orthogonal, _>(input, u8::eq)
+        .regions
+        .iter()
+        .map(|plot| plot.area() * plot.sides)
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT2: &str = "OOOOO
@@ -41,7 +50,7 @@ MMMISSJEEE";
 
         #[test]
         fn example2() {
-            assert_eq!(solve_part1(&generate(SAMPLE_INPUT2)), 1930);
+            assert_eq!(solve_part1(&generate(SAMPLE_INPUT2)), 772);
         }
 
         #[test]
@@ -51,7 +60,7 @@ MMMISSJEEE";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(12))), todo!());
+            crate::check_answer(12, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -60,12 +69,12 @@ MMMISSJEEE";
 
         #[test]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT3)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT3)), 1206);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(12))), todo!());
+            crate::check_answer(12, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -1,8 +1,11 @@
+use aoc_helpers::linear;
+
 const BUTTON_A_TOKENS: usize = 3;
 const BUTTON_B_TOKENS: usize = 1;
 
 type Button = (usize, usize);
 
+#[derive(Copy, Clone)]
 struct Machine {
     button_a: Button,
     button_b: Button,
@@ -10,41 +13,27 @@ struct Machine {
 }
 
 impl Machine {
+    /// Find the cheapest number of (A, B) presses which lands on the prize, if there is one
     fn calculate_minimal_presses(&self) -> Option<(usize, usize)> {
-        // strategy: try to make the prize by only pressing B, then progressively
-        // remove Bs and fill with As until we get a valid answer
-
-        if self.prize.0 % self.button_b.0 == 0 && self.prize.1 % self.button_b.1 == 0 {
-            return Some((
-                self.prize.0 / self.button_b.0,
-                self.prize.1 / self.button_b.1,
-            ));
-        }
-
-        let start_bs = std::cmp::max(
-            (self.prize.0 / self.button_b.0) + 1,
-            (self.prize.1 / self.button_b.1) + 1,
-        );
-
-        for num_bs in (0..start_bs).rev() {
-            let b_xtotal = self.button_b.0 * num_bs;
-            let b_ytotal = self.button_b.1 * num_bs;
-
-            let Some(xrem) = self.prize.0.checked_sub(b_xtotal) else {
-                continue;
-            };
-            let Some(yrem) = self.prize.1.checked_sub(b_ytotal) else {
-                continue;
-            };
-
-            let num_as = xrem / self.button_a.0;
+        let coeff = |x: usize| i64::try_from(x).expect("machine too large");
+        let matrix = [
+            vec![coeff(self.button_a.0), coeff(self.button_b.0)],
+            vec![coeff(self.button_a.1), coeff(self.button_b.1)],
+        ];
+        let prize = [coeff(self.prize.0), coeff(self.prize.1)];
+        let tokens = [coeff(BUTTON_A_TOKENS), coeff(BUTTON_B_TOKENS)];
+
+        let (presses, _) = linear::minimise(&matrix, &prize, &tokens)?;
+        let presses = |i: usize| usize::try_from(presses[i]).expect("presses are non-negative");
+        Some((presses(0), presses(1)))
+    }
 
-            if num_as * self.button_a.0 == xrem && num_as * self.button_a.1 == yrem {
-                return Some((num_as, num_bs));
-            }
+    /// The same machine, with the prize moved further away along both axes
+    fn with_prize_offset(&self, offset: usize) -> Self {
+        Self {
+            prize: (self.prize.0 + offset, self.prize.1 + offset),
+            ..*self
         }
-
-        None
     }
 }
 
@@ -95,23 +84,31 @@ fn generate(input: &str) -> Vec<Machine> {
     parse::parse.parse(input).expect("parse error")
 }
 
-#[aoc(day13, part1)]
-fn solve_part1(input: &[Machine]) -> usize {
-    input
-        .iter()
-        .filter_map(Machine::calculate_minimal_presses)
+fn total_tokens(machines: impl Iterator<Item = Machine>) -> usize {
+    machines
+        .filter_map(|m| m.calculate_minimal_presses())
         .map(|(a_presses, b_presses)| (a_presses * BUTTON_A_TOKENS) + (b_presses * BUTTON_B_TOKENS))
         .sum()
 }
 
+#[aoc(day13, part1)]
+fn solve_part1(input: &[Machine]) -> usize {
+    total_tokens(input.iter().copied())
+}
+
+const PART2_PRIZE_OFFSET: usize = 10_000_000_000_000;
+
 #[aoc(day13, part2)]
-fn solve_part2(_input: &[Machine]) -> usize {
-    todo!()
+fn solve_part2(input: &[Machine]) -> usize {
+    total_tokens(
+        input
+            .iter()
+            .map(|m| m.with_prize_offset(PART2_PRIZE_OFFSET)),
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "Button A: X+94, Y+34
@@ -140,7 +137,7 @@ Prize: X=18641, Y=10279";
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(13))), 36571);
+            crate::check_answer(13, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -149,13 +146,12 @@ Prize: X=18641, Y=10279";
 
         #[test]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), 875_318_608_908);
         }
 
         #[test]
-        #[ignore]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(13))), todo!());
+            crate::check_answer(13, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
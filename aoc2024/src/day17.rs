@@ -1,13 +1,15 @@
 #![allow(clippy::cast_lossless)]
 
+use std::fmt::{self, Write};
+
 #[derive(Clone, Debug)]
 struct Computer {
     prog: Vec<u8>,
     pc: usize,
-    output: Vec<u32>,
-    rega: u32,
-    regb: u32,
-    regc: u32,
+    output: Vec<u8>,
+    rega: u64,
+    regb: u64,
+    regc: u64,
 }
 
 mod parse {
@@ -25,7 +27,7 @@ mod parse {
         preceded("Program: ", separated(0.., single_u8, ',')).parse_next(input)
     }
 
-    fn num(input: &mut &str) -> Result<u32> {
+    fn num(input: &mut &str) -> Result<u64> {
         digit1.parse_to().parse_next(input)
     }
 
@@ -55,17 +57,243 @@ fn generate(input: &str) -> Computer {
     parse::computer.parse(input).expect("parse error")
 }
 
-impl Computer {
-    fn combo_opr(&self, operand: u8) -> u32 {
+/// One of the three registers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Register {
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A decoded combo operand
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Combo {
+    Literal(u8),
+    Register(Register),
+    /// Operand 7, which isn't valid in a real program
+    Reserved,
+}
+
+impl Combo {
+    fn decode(operand: u8) -> Self {
         match operand {
-            0..=3 => operand as u32,
-            4 => self.rega,
-            5 => self.regb,
-            6 => self.regc,
-            7 => panic!("reserved operand dereferenced"),
+            0..=3 => Self::Literal(operand),
+            4 => Self::Register(Register::A),
+            5 => Self::Register(Register::B),
+            6 => Self::Register(Register::C),
+            7 => Self::Reserved,
             _ => panic!("bad combo operand byte {operand}"),
         }
     }
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(n) => write!(f, "{n}"),
+            Self::Register(reg) => write!(f, "{reg}"),
+            Self::Reserved => write!(f, "?"),
+        }
+    }
+}
+
+/// A decoded instruction, along with its operand
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Instruction {
+    /// `A = A >> combo`
+    Adv(Combo),
+    /// `B = B ^ literal`
+    Bxl(u8),
+    /// `B = combo % 8`
+    Bst(Combo),
+    /// Jump to `literal` if A isn't zero
+    Jnz(u8),
+    /// `B = B ^ C`, ignoring the operand
+    Bxc,
+    /// Output `combo % 8`
+    Out(Combo),
+    /// `B = A >> combo`
+    Bdv(Combo),
+    /// `C = A >> combo`
+    Cdv(Combo),
+}
+
+impl Instruction {
+    fn decode(opcode: u8, operand: u8) -> Self {
+        match opcode {
+            0 => Self::Adv(Combo::decode(operand)),
+            1 => Self::Bxl(operand),
+            2 => Self::Bst(Combo::decode(operand)),
+            3 => Self::Jnz(operand),
+            4 => Self::Bxc,
+            5 => Self::Out(Combo::decode(operand)),
+            6 => Self::Bdv(Combo::decode(operand)),
+            7 => Self::Cdv(Combo::decode(operand)),
+            other => panic!("bad opcode {other}"),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Adv(combo) => write!(f, "adv {combo}"),
+            Self::Bxl(literal) => write!(f, "bxl {literal}"),
+            Self::Bst(combo) => write!(f, "bst {combo}"),
+            Self::Jnz(literal) => write!(f, "jnz {literal}"),
+            Self::Bxc => write!(f, "bxc"),
+            Self::Out(combo) => write!(f, "out {combo}"),
+            Self::Bdv(combo) => write!(f, "bdv {combo}"),
+            Self::Cdv(combo) => write!(f, "cdv {combo}"),
+        }
+    }
+}
+
+/// An expression over the values the registers held at the start of the loop, used to decompile
+/// programs
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Expr {
+    Literal(u64),
+    Register(Register),
+    /// The reserved combo operand, which the program would crash on if it were ever used
+    Reserved,
+    Shr(Box<Expr>, Box<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+}
+
+impl Expr {
+    fn shr(self, rhs: Expr) -> Expr {
+        match (self, rhs) {
+            (Self::Literal(a), Self::Literal(b)) => Self::Literal(
+                u32::try_from(b)
+                    .ok()
+                    .and_then(|shift| a.checked_shr(shift))
+                    .unwrap_or(0),
+            ),
+            (lhs, Self::Literal(0)) => lhs,
+            (lhs, rhs) => Self::Shr(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    /// Xor, folding runs of literals together
+    fn xor(self, rhs: Expr) -> Expr {
+        match (self, rhs) {
+            (Self::Literal(a), Self::Literal(b)) => Self::Literal(a ^ b),
+            (lhs, Self::Literal(0)) | (Self::Literal(0), lhs) => lhs,
+            (Self::Xor(lhs, inner), Self::Literal(b)) if matches!(*inner, Self::Literal(_)) => {
+                lhs.xor(inner.xor(Self::Literal(b)))
+            }
+            (lhs, rhs) => Self::Xor(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn mod8(self) -> Expr {
+        match self {
+            Self::Literal(n) => Self::Literal(n % 8),
+            already @ Self::Mod8(_) => already,
+            other => Self::Mod8(Box::new(other)),
+        }
+    }
+
+    fn mentions(&self, reg: Register) -> bool {
+        match self {
+            Self::Literal(_) | Self::Reserved => false,
+            Self::Register(r) => *r == reg,
+            Self::Shr(lhs, rhs) | Self::Xor(lhs, rhs) => lhs.mentions(reg) || rhs.mentions(reg),
+            Self::Mod8(inner) => inner.mentions(reg),
+        }
+    }
+
+    /// Write a sub-expression, bracketed unless it's a single value or `same_op` allows it
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, same_op: bool) -> fmt::Result {
+        match self {
+            Self::Literal(_) | Self::Register(_) | Self::Reserved => write!(f, "{self}"),
+            Self::Xor(..) if same_op => write!(f, "{self}"),
+            _ => write!(f, "({self})"),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(n) => write!(f, "{n}"),
+            Self::Register(reg) => write!(f, "{reg}"),
+            Self::Reserved => write!(f, "?"),
+            Self::Shr(lhs, rhs) => {
+                lhs.fmt_operand(f, false)?;
+                write!(f, " >> ")?;
+                rhs.fmt_operand(f, false)
+            }
+            // Xor is associative, so chains of it don't need brackets
+            Self::Xor(lhs, rhs) => {
+                lhs.fmt_operand(f, true)?;
+                write!(f, " ^ ")?;
+                rhs.fmt_operand(f, true)
+            }
+            Self::Mod8(inner) => {
+                inner.fmt_operand(f, false)?;
+                write!(f, " % 8")
+            }
+        }
+    }
+}
+
+/// The registers' contents during decompilation, in terms of their values at the start
+struct Symbolic([Expr; 3]);
+
+impl Symbolic {
+    fn new() -> Self {
+        Self([Register::A, Register::B, Register::C].map(Expr::Register))
+    }
+
+    fn get(&self, reg: Register) -> Expr {
+        self.0[reg as usize].clone()
+    }
+
+    fn set(&mut self, reg: Register, expr: Expr) {
+        self.0[reg as usize] = expr;
+    }
+
+    fn combo(&self, combo: Combo) -> Expr {
+        match combo {
+            Combo::Literal(n) => Expr::Literal(n as u64),
+            Combo::Register(reg) => self.get(reg),
+            Combo::Reserved => Expr::Reserved,
+        }
+    }
+}
+
+impl Computer {
+    fn combo_opr(&self, combo: Combo) -> u64 {
+        match combo {
+            Combo::Literal(n) => n as u64,
+            Combo::Register(Register::A) => self.rega,
+            Combo::Register(Register::B) => self.regb,
+            Combo::Register(Register::C) => self.regc,
+            Combo::Reserved => panic!("reserved operand dereferenced"),
+        }
+    }
+
+    /// `A / 2^combo`, which is zero once the shift is wider than the register
+    fn divide_a(&self, combo: Combo) -> u64 {
+        u32::try_from(self.combo_opr(combo))
+            .ok()
+            .and_then(|shift| self.rega.checked_shr(shift))
+            .unwrap_or(0)
+    }
 
     fn step(&mut self) -> bool {
         let Some(&opcode) = self.prog.get(self.pc) else {
@@ -75,40 +303,208 @@ impl Computer {
             return true;
         };
 
-        match opcode {
-            // adv
-            0 => {
-                self.rega /= 2u32.pow(self.combo_opr(operand));
-            }
-            // bxl
-            1 => self.regb ^= operand as u32,
-            // bst
-            2 => self.regb = self.combo_opr(operand) % 8,
-            // jnz
-            3 => {
+        match Instruction::decode(opcode, operand) {
+            Instruction::Adv(combo) => self.rega = self.divide_a(combo),
+            Instruction::Bxl(literal) => self.regb ^= literal as u64,
+            Instruction::Bst(combo) => self.regb = self.combo_opr(combo) % 8,
+            Instruction::Jnz(literal) => {
                 if self.rega != 0 {
-                    self.pc = operand as usize;
+                    self.pc = literal as usize;
                     return false;
                 }
             }
-            // bxc
-            4 => self.regb ^= self.regc,
-            // out
-            5 => self.output.push(self.combo_opr(operand) % 8),
-            // bdv
-            6 => self.regb = self.rega / 2u32.pow(self.combo_opr(operand)),
-            // cdv
-            7 => self.regc = self.rega / 2u32.pow(self.combo_opr(operand)),
-            other => panic!("bad opcode {other}"),
+            Instruction::Bxc => self.regb ^= self.regc,
+            Instruction::Out(combo) => self.output.push((self.combo_opr(combo) % 8) as u8),
+            Instruction::Bdv(combo) => self.regb = self.divide_a(combo),
+            Instruction::Cdv(combo) => self.regc = self.divide_a(combo),
         }
 
         self.pc += 2;
         false
     }
 
+    /// Every instruction in the program along with its address, ignoring a trailing opcode with
+    /// no operand, which would just halt
+    fn instructions(&self) -> impl Iterator<Item = (usize, Instruction)> + '_ {
+        self.prog
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(i, pair)| (i * 2, Instruction::decode(pair[0], pair[1])))
+    }
+
+    /// List the program, one instruction per line, with combo operands shown as the register or
+    /// literal they refer to
+    fn disassemble(&self) -> String {
+        let mut listing = String::new();
+        for (addr, ins) in self.instructions() {
+            writeln!(listing, "{addr:>3}: {ins}").unwrap();
+        }
+        listing
+    }
+
+    /// Render the program as pseudo-code
+    ///
+    /// A program which is a single loop back to the start is written as a `do`/`while` loop with
+    /// each output given as an expression of the registers at the start of that time round,
+    /// followed by the registers carried over to the next time round.
+    /// Anything else is written out as the disassembly, with each instruction annotated with the
+    /// statement it's equivalent to.
+    fn decompile(&self) -> String {
+        let instructions: Vec<_> = self.instructions().map(|(_, ins)| ins).collect();
+        let single_loop = instructions.last() == Some(&Instruction::Jnz(0))
+            && instructions
+                .iter()
+                .filter(|ins| matches!(ins, Instruction::Jnz(_)))
+                .count()
+                == 1;
+        if !single_loop {
+            let mut code = String::new();
+            for (line, ins) in self.disassemble().lines().zip(instructions) {
+                writeln!(code, "{line:<12}// {}", statement(ins)).unwrap();
+            }
+            return code;
+        }
+
+        let mut regs = Symbolic::new();
+        let mut outputs = Vec::new();
+        for &ins in &instructions {
+            match ins {
+                Instruction::Adv(combo) => {
+                    regs.set(Register::A, regs.get(Register::A).shr(regs.combo(combo)));
+                }
+                Instruction::Bxl(literal) => {
+                    regs.set(
+                        Register::B,
+                        regs.get(Register::B).xor(Expr::Literal(literal as u64)),
+                    );
+                }
+                Instruction::Bst(combo) => regs.set(Register::B, regs.combo(combo).mod8()),
+                Instruction::Jnz(_) => {}
+                Instruction::Bxc => {
+                    regs.set(
+                        Register::B,
+                        regs.get(Register::B).xor(regs.get(Register::C)),
+                    );
+                }
+                Instruction::Out(combo) => outputs.push(regs.combo(combo).mod8()),
+                Instruction::Bdv(combo) => {
+                    regs.set(Register::B, regs.get(Register::A).shr(regs.combo(combo)));
+                }
+                Instruction::Cdv(combo) => {
+                    regs.set(Register::C, regs.get(Register::A).shr(regs.combo(combo)));
+                }
+            }
+        }
+
+        // A is always needed for the loop condition, and B or C only if something reads the value
+        // left over from the last time round
+        let mut needed = vec![Register::A];
+        for reg in [Register::B, Register::C] {
+            let read = outputs.iter().any(|out| out.mentions(reg))
+                || [Register::A, Register::B, Register::C]
+                    .iter()
+                    .any(|&other| regs.get(other).mentions(reg));
+            if read {
+                needed.push(reg);
+            }
+        }
+        needed.retain(|&reg| regs.get(reg) != Expr::Register(reg));
+
+        let mut code = String::from("do {\n");
+        for out in outputs {
+            writeln!(code, "    out({out})").unwrap();
+        }
+        match needed[..] {
+            [] => {}
+            [reg] => writeln!(code, "    {reg} = {}", regs.get(reg)).unwrap(),
+            _ => {
+                let names: Vec<_> = needed.iter().map(Register::to_string).collect();
+                let exprs: Vec<_> = needed
+                    .iter()
+                    .map(|&reg| regs.get(reg).to_string())
+                    .collect();
+                let (names, exprs) = (names.join(", "), exprs.join(", "));
+                writeln!(code, "    ({names}) = ({exprs})").unwrap();
+            }
+        }
+        code += "} while A != 0\n";
+        code
+    }
+
     fn run_until_halt(&mut self) {
         while !self.step() {}
     }
+
+    /// Run the program from the start with register A set to `rega`, returning its output
+    fn output_for(&self, rega: u64) -> Vec<u8> {
+        let mut comp = Self {
+            rega,
+            pc: 0,
+            output: Vec::new(),
+            ..self.clone()
+        };
+        comp.run_until_halt();
+        comp.output
+    }
+
+    /// Check the program is a single loop which shifts A right by 3 bits each time round, and
+    /// loops back to the start until A is zero
+    fn is_shift_by_3_loop(&self) -> bool {
+        let instructions: Vec<_> = self.instructions().map(|(_, ins)| ins).collect();
+        let shifts = instructions
+            .iter()
+            .filter(|&&ins| ins == Instruction::Adv(Combo::Literal(3)))
+            .count();
+        let jumps = instructions
+            .iter()
+            .filter(|ins| matches!(ins, Instruction::Jnz(_)))
+            .count();
+        shifts == 1 && jumps == 1 && instructions.last() == Some(&Instruction::Jnz(0))
+    }
+
+    /// Find the lowest initial value of register A for which the program outputs itself
+    ///
+    /// Each time round the loop outputs one value and drops the lowest octal digit of A, so the
+    /// last value output only depends on the highest digit, the one before only on the top two,
+    /// and so on.
+    /// Working backwards from the end of the program, this tries every octal digit which keeps the
+    /// output matching, backtracking when no digit works.
+    fn find_quine(&self) -> Option<u64> {
+        assert!(
+            self.is_shift_by_3_loop(),
+            "program doesn't have the usual shift-by-3 loop structure:\n{}",
+            self.decompile()
+        );
+        self.find_quine_from(0, self.prog.len())
+    }
+
+    /// Extend `prefix` (the highest octal digits of A found so far) until the program outputs
+    /// everything from `matched - 1` onwards, and eventually the whole program
+    fn find_quine_from(&self, prefix: u64, matched: usize) -> Option<u64> {
+        if matched == 0 {
+            return Some(prefix);
+        }
+        (0..8)
+            .map(|digit| prefix << 3 | digit)
+            // A starting value of zero would never be extended any further
+            .filter(|&rega| rega != 0)
+            .filter(|&rega| self.output_for(rega) == self.prog[matched - 1..])
+            .find_map(|rega| self.find_quine_from(rega, matched - 1))
+    }
+}
+
+/// A single instruction as a line of pseudo-code
+fn statement(ins: Instruction) -> String {
+    match ins {
+        Instruction::Adv(combo) => format!("A = A >> {combo}"),
+        Instruction::Bxl(literal) => format!("B = B ^ {literal}"),
+        Instruction::Bst(combo) => format!("B = {combo} % 8"),
+        Instruction::Jnz(literal) => format!("if A != 0 goto {literal}"),
+        Instruction::Bxc => "B = B ^ C".to_string(),
+        Instruction::Out(combo) => format!("out({combo} % 8)"),
+        Instruction::Bdv(combo) => format!("B = A >> {combo}"),
+        Instruction::Cdv(combo) => format!("C = A >> {combo}"),
+    }
 }
 
 #[aoc(day17, part1)]
@@ -123,13 +519,14 @@ fn solve_part1(input: &Computer) -> String {
 }
 
 #[aoc(day17, part2)]
-fn solve_part2(_input: &Computer) -> usize {
-    todo!()
+fn solve_part2(input: &Computer) -> u64 {
+    input
+        .find_quine()
+        .expect("no starting value makes the program output itself")
 }
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     const SAMPLE_INPUT: &str = "Register A: 729
@@ -138,6 +535,12 @@ Register C: 0
 
 Program: 0,1,5,4,3,0";
 
+    const SAMPLE_INPUT_PART2: &str = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+
     mod part1 {
         use super::*;
 
@@ -148,10 +551,7 @@ Program: 0,1,5,4,3,0";
 
         #[test]
         fn mine() {
-            assert_eq!(
-                solve_part1(&generate(&crate::get_input(17))),
-                "3,6,3,7,0,7,0,3,0"
-            );
+            crate::check_answer(17, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -160,12 +560,77 @@ Program: 0,1,5,4,3,0";
 
         #[test]
         fn example() {
-            assert_eq!(solve_part2(&generate(SAMPLE_INPUT)), todo!());
+            assert_eq!(solve_part2(&generate(SAMPLE_INPUT_PART2)), 117_440);
         }
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(17))), todo!());
+            crate::check_answer(17, 2, |input| solve_part2(&generate(input)));
+        }
+    }
+
+    mod decompile {
+        use super::*;
+
+        #[test]
+        fn disassembly() {
+            aoc_helpers::input::with_input(2024, 17, |input| {
+                assert_eq!(
+                    generate(input).disassemble(),
+                    "  0: bst A\n  2: bxl 5\n  4: cdv B\n  6: bxl 6\n  8: adv 3\n 10: bxc\n 12: out B\n 14: jnz 0\n"
+                );
+            });
+        }
+
+        #[test]
+        fn example() {
+            assert_eq!(
+                generate(SAMPLE_INPUT_PART2).decompile(),
+                "do {\n    out((A >> 3) % 8)\n    A = A >> 3\n} while A != 0\n"
+            );
+        }
+
+        #[test]
+        fn mine() {
+            aoc_helpers::input::with_input(2024, 17, |input| {
+                assert_eq!(
+                    generate(input).decompile(),
+                    "do {\n    out(((A % 8) ^ 3 ^ (A >> ((A % 8) ^ 5))) % 8)\n    A = A >> 3\n} while A != 0\n"
+                );
+            });
+        }
+
+        #[test]
+        fn carried_register() {
+            let comp =
+                generate("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 1,1,5,5,0,3,3,0");
+            assert_eq!(
+                comp.decompile(),
+                "do {\n    out((B ^ 1) % 8)\n    (A, B) = (A >> 3, B ^ 1)\n} while A != 0\n"
+            );
+        }
+
+        #[test]
+        fn not_a_single_loop() {
+            let comp =
+                generate("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 3,4,4,0,5,6,3,0");
+            assert_eq!(
+                comp.decompile(),
+                "  0: jnz 4  // if A != 0 goto 4\n  2: bxc    // B = B ^ C\n  4: out C  // out(C % 8)\n  6: jnz 0  // if A != 0 goto 0\n"
+            );
+        }
+
+        #[test]
+        fn reserved_operand() {
+            let comp = generate("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7,3,0");
+            assert_eq!(comp.decompile(), "do {\n    out(? % 8)\n} while A != 0\n");
+        }
+
+        #[test]
+        #[should_panic(expected = "doesn't have the usual shift-by-3 loop structure")]
+        fn reserved_operand_quine() {
+            let comp = generate("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7,3,0");
+            comp.find_quine();
         }
     }
 }
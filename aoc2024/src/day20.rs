@@ -1,5 +1,8 @@
-use crate::grid::{Grid, Point};
 use crate::iter_ext::IterExt;
+use aoc_helpers::{
+    grids::{self, Orthogonal},
+    manhattan, search, UPoint as Point,
+};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Square {
@@ -9,46 +12,23 @@ enum Square {
 
 #[aoc_generator(day20)]
 fn generate(input: &str) -> Vec<Point> {
-    let mut start = Point { x: 0, y: 0 };
-    let mut end = Point { x: 0, y: 0 };
-
-    let mut grid = Vec::new();
-    for (y, line) in input.lines().enumerate() {
-        let mut parsed_line = Vec::with_capacity(line.len());
-        for (x, c) in line.chars().enumerate() {
-            parsed_line.push(match c {
-                '#' => Square::Wall,
-                '.' => Square::Space,
-                'S' => {
-                    start = (x, y).into();
-                    Square::Space
-                }
-                'E' => {
-                    end = (x, y).into();
-                    Square::Space
-                }
-                _ => panic!("unsupported square char {c}"),
-            });
-        }
-        grid.push(parsed_line);
-    }
-
-    let maze: Grid<Square, crate::grid::Orthogonal> = Grid::new(grid);
-    let mut visited = Vec::new();
-    let mut current = start;
-
-    while current != end {
-        let last = *visited.last().unwrap_or(&start);
-        let next = maze
-            .neighbours(current)
-            .find(|&(p, sq)| p != last && sq != &Square::Wall)
-            .expect("found a dead end")
-            .0;
-        visited.push(current);
-        current = next;
-    }
-
-    visited
+    let parsed = grids::parse(input, b"SE", |b| match b {
+        b'#' => Some(Square::Wall),
+        b'.' | b'S' | b'E' => Some(Square::Space),
+        _ => None,
+    })
+    .expect("invalid maze");
+    let start = parsed.single(b'S').expect("no unique start found");
+    let end = parsed.single(b'E').expect("no unique end found");
+    let maze = parsed.grid;
+
+    search::bfs(
+        [start],
+        search::grid_steps::<Orthogonal, _>(&maze, |_, to| to != &Square::Wall),
+        |&p| p == end,
+    )
+    .expect("no route through the maze")
+    .nodes
 }
 
 const LOOK_AHEAD: usize = 4;
@@ -60,7 +40,7 @@ fn count_cheats<const MAX_CHEAT_LEN: usize>(route: &[Point]) -> usize {
         .enumerate()
         .cart_prod_with(|(start, _)| route.iter().enumerate().skip(start + LOOK_AHEAD))
         .filter(|((skip_start, &start), (skip_end, &end))| {
-            let cheat_len = start.dist(end);
+            let cheat_len = manhattan::distu(start, end);
             if cheat_len > MAX_CHEAT_LEN {
                 return false;
             }
@@ -84,7 +64,6 @@ fn solve_part2(route: &[Point]) -> usize {
 
 #[cfg(test)]
 mod tests {
-    #![allow(unreachable_code)]
     use super::*;
 
     mod part1 {
@@ -92,7 +71,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part1(&generate(&crate::get_input(20))), 1445);
+            crate::check_answer(20, 1, |input| solve_part1(&generate(input)));
         }
     }
 
@@ -101,7 +80,7 @@ mod tests {
 
         #[test]
         fn mine() {
-            assert_eq!(solve_part2(&generate(&crate::get_input(20))), todo!());
+            crate::check_answer(20, 2, |input| solve_part2(&generate(input)));
         }
     }
 }
@@ -3,15 +3,12 @@
 #[macro_use]
 extern crate aoc_runner_derive;
 
+/// Check a solution against my recorded answer, see [`aoc_helpers::answers::assert_answer`]
 #[cfg(test)]
-fn get_input(day: u32) -> String {
-    std::fs::read_to_string(format!("./input/2024/day{day}.txt"))
-        .unwrap()
-        .trim()
-        .to_owned()
+fn check_answer<T: std::fmt::Display>(day: u32, part: u32, solve: impl FnOnce(&str) -> T) {
+    aoc_helpers::answers::assert_answer(2024, day, part, solve);
 }
 
-pub mod grid;
 pub mod iter_ext;
 
 mod day01;
@@ -23,10 +20,10 @@ mod day06;
 mod day07;
 mod day08;
 mod day09;
+mod day10;
 mod day11;
 mod day12;
 mod day13;
 mod day17;
 mod day20;
-mod day10;
 aoc_lib! { year = 2024 }
@@ -0,0 +1,54 @@
+//! Criterion benchmarks for every registered solver with an input, named `YEAR/dayN/STAGE`
+//!
+//! Each day's generator is timed as `parse`, apart from its solutions, which are timed as
+//! `part1`, `part2`, or e.g. `part1/Bytes` for alternatives, on input that's already been parsed.
+//! Parts which fail, including unfinished ones, are left out.
+//! Filter and compare with a baseline as usual for criterion, e.g.
+//! `cargo bench -- --save-baseline before 2022/day7` and then `--baseline before`.
+
+use aoc_cli::registry::{self, Solver};
+use aoc_cli::run;
+use aoc_helpers::input::Inputs;
+use aoc_runner::ArcStr;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_day(c: &mut Criterion, solvers: &[&Solver], text: &str) {
+    let (year, day) = (solvers[0].year, solvers[0].day);
+    let mut group = c.benchmark_group(format!("{}/day{}", year, day));
+    let input = ArcStr::from(text);
+
+    let mut parsed = false;
+    for solver in solvers {
+        if run::run(solver, text).is_err() {
+            continue;
+        }
+        if !parsed {
+            group.bench_function("parse", |b| b.iter(|| (solver.factory)(input.clone())));
+            parsed = true;
+        }
+
+        let runner = (solver.factory)(input.clone()).unwrap();
+        let id = match solver.name {
+            Some(name) => format!("part{}/{}", solver.part, name),
+            None => format!("part{}", solver.part),
+        };
+        group.bench_function(id, |b| b.iter(|| runner.try_run().unwrap()));
+    }
+    group.finish();
+}
+
+fn solvers(c: &mut Criterion) {
+    let inputs = Inputs::from_env().unwrap_or_else(|err| panic!("{}", err));
+    // Unfinished parts panic, which is reported well enough by leaving them out
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let solvers = registry::find(None, None, None);
+    for day in solvers.chunk_by(|a, b| (a.year, a.day) == (b.year, b.day)) {
+        if let Ok(input) = inputs.load(day[0].year, day[0].day) {
+            bench_day(c, day, &input);
+        }
+    }
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
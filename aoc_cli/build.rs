@@ -0,0 +1,133 @@
+//! Finds every solver registered with `#[aoc(dayN, partK)]` in the year crates this crate depends
+//! on, and writes out a table of them for `src/registry.rs` to include.
+//!
+//! The year crates are whichever `aocYYYY` dependencies are listed in `Cargo.toml`, and only
+//! modules declared in their `lib.rs` are searched, which matches what actually gets compiled.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Entry {
+    year: u32,
+    day: u32,
+    part: u32,
+    name: Option<String>,
+}
+
+/// Years of every `aocYYYY = ...` dependency
+fn years(manifest: &str) -> Vec<u32> {
+    manifest
+        .lines()
+        .filter_map(|line| line.strip_prefix("aoc"))
+        .filter_map(|rest| rest.split_once('='))
+        .filter_map(|(year, _)| year.trim().parse().ok())
+        .collect()
+}
+
+/// Names of the modules declared in a `lib.rs`
+fn modules(lib: &str) -> Vec<String> {
+    lib.lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("pub ").or(Some(line)))
+        .filter_map(|line| line.strip_prefix("mod "))
+        .filter_map(|line| line.strip_suffix(';'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `(day, part, name)` for every `#[aoc(...)]` attribute in a source file
+fn attributes(source: &str) -> Vec<(u32, u32, Option<String>)> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#[aoc("))
+        .filter_map(|line| line.strip_suffix(")]"))
+        .filter_map(|args| {
+            let mut args = args.split(',').map(str::trim);
+            let day = args.next()?.strip_prefix("day")?.parse().ok()?;
+            let part = args.next()?.strip_prefix("part")?.parse().ok()?;
+            let name = args.next().map(str::to_string);
+            Some((day, part, name))
+        })
+        .collect()
+}
+
+fn module_path(src: &Path, module: &str) -> PathBuf {
+    let file = src.join(format!("{}.rs", module));
+    if file.exists() {
+        file
+    } else {
+        src.join(module).join("mod.rs")
+    }
+}
+
+/// The path to the `Factory` method `aoc_lib!` generates for a solver
+fn factory(entry: &Entry) -> String {
+    let (snake, camel) = match &entry.name {
+        Some(name) => (
+            format!(
+                "day{}_part{}_{}",
+                entry.day,
+                entry.part,
+                name.to_lowercase()
+            ),
+            format!("Day{}Part{}{}", entry.day, entry.part, name.to_uppercase()),
+        ),
+        None => (
+            format!("day{}_part{}", entry.day, entry.part),
+            format!("Day{}Part{}", entry.day, entry.part),
+        ),
+    };
+    format!(
+        "<aoc{year}::Factory as aoc{year}::{camel}>::{snake}",
+        year = entry.year
+    )
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+    let manifest_path = manifest_dir.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+
+    let mut entries = Vec::new();
+    for year in years(&fs::read_to_string(&manifest_path).unwrap()) {
+        let src = root.join(format!("aoc{}", year)).join("src");
+        println!("cargo:rerun-if-changed={}", src.display());
+
+        let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+        for module in modules(&lib) {
+            let path = module_path(&src, &module);
+            println!("cargo:rerun-if-changed={}", path.display());
+            let source = fs::read_to_string(&path).unwrap();
+            for (day, part, name) in attributes(&source) {
+                entries.push(Entry {
+                    year,
+                    day,
+                    part,
+                    name,
+                });
+            }
+        }
+    }
+    entries.sort_by(|a, b| (a.year, a.day, a.part, &a.name).cmp(&(b.year, b.day, b.part, &b.name)));
+
+    let mut out = String::from("pub static SOLVERS: &[Solver] = &[\n");
+    for entry in &entries {
+        writeln!(
+            out,
+            "    Solver {{ year: {}, day: {}, part: {}, name: {:?}, factory: {} }},",
+            entry.year,
+            entry.day,
+            entry.part,
+            entry.name,
+            factory(entry)
+        )
+        .unwrap();
+    }
+    out += "];\n";
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solvers.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -0,0 +1,281 @@
+//! Timing solvers repeatedly, and comparing the timings with a saved baseline
+//!
+//! Each solver's generator and solution are timed separately over a number of samples, keeping the
+//! median of each. Summaries are saved as JSON, so that a later run can be compared with one and
+//! anything which has got slower by more than a threshold flagged as a regression.
+
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_runner::ArcStr;
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Solver;
+use crate::run::{self, RunError};
+
+/// Changes smaller than this are noise however large they are relatively
+pub const NOISE_FLOOR: Duration = Duration::from_micros(5);
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io(PathBuf, io::Error),
+    Json(PathBuf, serde_json::Error),
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            Self::Json(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+/// The median timings of one solver
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub samples: usize,
+    /// Running the generator, in nanoseconds
+    pub parse_ns: u64,
+    /// Running the solution on the generator's output, in nanoseconds
+    pub run_ns: u64,
+}
+
+impl Measurement {
+    pub fn parse(&self) -> Duration {
+        Duration::from_nanos(self.parse_ns)
+    }
+
+    pub fn run(&self) -> Duration {
+        Duration::from_nanos(self.run_ns)
+    }
+
+    fn matches(&self, other: &Measurement) -> bool {
+        (self.year, self.day, self.part, &self.name)
+            == (other.year, other.day, other.part, &other.name)
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)?;
+        if let Some(name) = &self.name {
+            write!(f, " ({})", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// The timings from one run of the benchmarks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Summary {
+    pub fn load(path: &Path) -> Result<Summary, BenchError> {
+        let text = fs::read_to_string(path).map_err(|e| BenchError::Io(path.to_path_buf(), e))?;
+        serde_json::from_str(&text).map_err(|e| BenchError::Json(path.to_path_buf(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchError> {
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| BenchError::Json(path.to_path_buf(), e))?;
+        fs::write(path, text + "\n").map_err(|e| BenchError::Io(path.to_path_buf(), e))
+    }
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Time a solver on `input` `samples` times
+///
+/// It's run once first without being timed, so that solvers which fail, including unfinished ones,
+/// are reported without spending any time on them.
+pub fn measure(solver: &Solver, input: &str, samples: usize) -> Result<Measurement, RunError> {
+    run::run(solver, input)?;
+
+    let input = ArcStr::from(input);
+    let samples = samples.max(1);
+    let mut parse = Vec::with_capacity(samples);
+    let mut run = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let start = Instant::now();
+        let runner =
+            (solver.factory)(input.clone()).map_err(|e| RunError::Generator(e.to_string()))?;
+        let parsed = Instant::now();
+        let answer = runner
+            .try_run()
+            .map_err(|e| RunError::Solver(e.to_string()))?;
+        run.push(parsed.elapsed());
+        parse.push(parsed - start);
+        black_box(answer);
+    }
+
+    Ok(Measurement {
+        year: solver.year,
+        day: solver.day,
+        part: solver.part,
+        name: solver.name.map(str::to_string),
+        samples,
+        parse_ns: nanos(median(parse)),
+        run_ns: nanos(median(run)),
+    })
+}
+
+/// A stage of a solver which is slower than in the baseline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub solver: String,
+    /// Which stage got slower, `parse` or `run`
+    pub stage: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl fmt::Display for Regression {
+    /// The change is left out when the stage took no measurable time before, as it has no size
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {:?} -> {:?}",
+            self.solver, self.stage, self.before, self.after
+        )?;
+        if !self.before.is_zero() {
+            let change = self.after.as_secs_f64() / self.before.as_secs_f64() - 1.0;
+            write!(f, " (+{:.0}%)", change * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every stage which has got more than `threshold` slower than in `baseline`, as a fraction
+///
+/// Solvers missing from either summary are left out, as are changes below [`NOISE_FLOOR`].
+pub fn compare(baseline: &Summary, current: &Summary, threshold: f64) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    for after in &current.measurements {
+        let Some(before) = baseline.measurements.iter().find(|m| m.matches(after)) else {
+            continue;
+        };
+        let stages = [
+            ("parse", before.parse(), after.parse()),
+            ("run", before.run(), after.run()),
+        ];
+        for (stage, before, after_time) in stages {
+            let limit = before.mul_f64(1.0 + threshold);
+            if after_time > limit && after_time - before > NOISE_FLOOR {
+                regressions.push(Regression {
+                    solver: after.to_string(),
+                    stage,
+                    before,
+                    after: after_time,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::registry::find;
+
+    fn measurement(day: u32, parse_us: u64, run_us: u64) -> Measurement {
+        Measurement {
+            year: 2022,
+            day,
+            part: 1,
+            name: None,
+            samples: 10,
+            parse_ns: parse_us * 1000,
+            run_ns: run_us * 1000,
+        }
+    }
+
+    #[test]
+    fn measures() {
+        let solver = find(Some(2021), Some(1), Some(1))[0];
+        let measurement = measure(solver, "199\n200\n208\n210\n200\n207", 5).unwrap();
+        assert_eq!(
+            (measurement.year, measurement.day, measurement.part),
+            (2021, 1, 1)
+        );
+        assert_eq!(measurement.samples, 5);
+        assert!(matches!(
+            measure(solver, "deep", 5),
+            Err(RunError::Panic(_))
+        ));
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let baseline = Summary {
+            measurements: vec![measurement(1, 100, 1000), measurement(2, 1, 1)],
+        };
+        let current = Summary {
+            measurements: vec![
+                measurement(1, 105, 1500),
+                measurement(2, 3, 3),
+                measurement(3, 100, 100),
+            ],
+        };
+
+        let regressions = compare(&baseline, &current, 0.1);
+        assert_eq!(
+            regressions,
+            [Regression {
+                solver: "2022 day 1 part 1".to_string(),
+                stage: "run",
+                before: Duration::from_millis(1),
+                after: Duration::from_micros(1500),
+            }]
+        );
+        assert_eq!(
+            regressions[0].to_string(),
+            "2022 day 1 part 1 run: 1ms -> 1.5ms (+50%)"
+        );
+        assert!(compare(&baseline, &current, 0.6).is_empty());
+
+        let baseline = Summary {
+            measurements: vec![measurement(1, 0, 0)],
+        };
+        let regressions = compare(&baseline, &current, 0.1);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(
+            regressions[1].to_string(),
+            "2022 day 1 part 1 run: 0ns -> 1.5ms"
+        );
+    }
+
+    #[test]
+    fn summaries_round_trip() {
+        let summary = Summary {
+            measurements: vec![measurement(1, 100, 1000)],
+        };
+        let path = env::temp_dir().join(format!("aoc_cli_bench_{}.json", std::process::id()));
+        summary.save(&path).unwrap();
+        assert_eq!(Summary::load(&path).unwrap(), summary);
+        fs::remove_file(path).unwrap();
+    }
+}
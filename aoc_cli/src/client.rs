@@ -0,0 +1,581 @@
+//! Downloading inputs from, and submitting answers to, the Advent of Code website
+//!
+//! Requests are made with the session cookie of a logged in browser, which is read from
+//! `AOC_SESSION` or `session` under `[client]` in `aoc.toml`.
+//! The base URL can be changed the same way, with `AOC_BASE_URL` or `base_url`, so that everything
+//! can be pointed at a local server instead.
+//!
+//! Inputs are saved wherever [`Inputs`] says they belong, and never fetched again once they're
+//! there. Requests are spaced out by at least [`DEFAULT_THROTTLE`] to go easy on the servers,
+//! including across separate runs, as the time of the last request is kept in a file in the
+//! system's temporary directory.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_helpers::answer::Answer;
+use aoc_helpers::input::{self, InputError, Inputs};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Name of the file holding the time of the last request, in the system's temporary directory
+pub const STAMP_FILE: &str = "aoc_cli_last_request";
+
+const USER_AGENT: &str = concat!(
+    "aoc_cli/",
+    env!("CARGO_PKG_VERSION"),
+    " (personal Advent of Code runner)"
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token is configured
+    NoSession,
+    /// The config file couldn't be read or isn't valid
+    Config(PathBuf, String),
+    /// The server replied with an error status, e.g. 404 for a puzzle that isn't out yet
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The request didn't get a reply at all
+    Transport {
+        url: String,
+        reason: String,
+    },
+    /// The reply to a submission didn't say whether the answer was right
+    UnexpectedResponse(String),
+    /// Images can't be submitted, only the letters they spell out
+    Image,
+    /// The fetched input couldn't be saved
+    Save(PathBuf, io::Error),
+    Input(InputError),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSession => write!(
+                f,
+                "no session token, set {} or session under [client] in {}",
+                SESSION_VAR,
+                input::CONFIG_FILE
+            ),
+            Self::Config(path, reason) => write!(f, "{}: {}", path.display(), reason),
+            Self::Status { url, status } => write!(f, "{} replied with status {}", url, status),
+            Self::Transport { url, reason } => write!(f, "request to {} failed: {}", url, reason),
+            Self::UnexpectedResponse(text) => write!(f, "unexpected response: {}", text),
+            Self::Image => write!(f, "images can't be submitted, only the letters they show"),
+            Self::Save(path, err) => write!(f, "can't save {}: {}", path.display(), err),
+            Self::Input(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<InputError> for ClientError {
+    fn from(err: InputError) -> Self {
+        Self::Input(err)
+    }
+}
+
+/// What the site made of a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with how much longer to wait if the page said
+    RateLimited(Option<Duration>),
+    /// The part has already been solved, or isn't unlocked yet
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "incorrect, too high"),
+            Self::TooLow => write!(f, "incorrect, too low"),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, try again in {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => write!(f, "rate limited"),
+            Self::WrongLevel => write!(f, "already solved, or not unlocked yet"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, with tags removed and whitespace collapsed
+fn article_text(html: &str) -> &str {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+    &html[start..end]
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse a wait like "1m 5s" out of "You have 1m 5s left to wait"
+fn parse_wait(text: &str) -> Option<Duration> {
+    let before = text.split(" left to wait").next()?;
+    let wait = before.rsplit("You have ").next()?;
+    wait.split_whitespace()
+        .map(|part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            match unit {
+                "h" => Some(n * 3600),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl Verdict {
+    /// Read the verdict from the page returned after submitting an answer
+    pub fn parse(html: &str) -> Result<Verdict, ClientError> {
+        let text = strip_tags(article_text(html));
+        let verdict = if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait(&text))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return Err(ClientError::UnexpectedResponse(text));
+        };
+        Ok(verdict)
+    }
+}
+
+/// Where to send requests, and who to send them as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub base_url: String,
+    pub session: Option<String>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Read the `[client]` table of the config file if there is one, then apply any environment
+    /// variables on top
+    pub fn from_env() -> Result<ClientConfig, ClientError> {
+        let path = input::config_path();
+        let mut config = match fs::read_to_string(&path) {
+            Ok(text) => Self::from_config(&text).map_err(|e| ClientError::Config(path, e))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => ClientConfig::default(),
+            Err(err) => return Err(ClientError::Config(path, err.to_string())),
+        };
+
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    pub fn from_config(text: &str) -> Result<ClientConfig, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let mut config = ClientConfig::default();
+        let Some(section) = table.get("client") else {
+            return Ok(config);
+        };
+
+        for (key, value) in section.as_table().into_iter().flatten() {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("client.{} should be a string", key))?
+                .to_string();
+            match key.as_str() {
+                "base_url" => config.base_url = value,
+                "session" => config.session = Some(value),
+                _ => return Err(format!("unknown setting client.{}", key)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    inputs: Inputs,
+    throttle: Duration,
+    last_request: Option<SystemTime>,
+    /// Where the time of the last request is shared with other runs, if anywhere
+    stamp: Option<PathBuf>,
+}
+
+/// The time saved in a stamp file, in milliseconds since the epoch
+fn read_stamp(path: &Path) -> Option<SystemTime> {
+    let millis = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+}
+
+fn write_stamp(path: &Path, time: SystemTime) -> io::Result<()> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    fs::write(path, millis.to_string())
+}
+
+impl Client {
+    /// A client saving inputs to `inputs`, throttled by [`DEFAULT_THROTTLE`]
+    pub fn new(config: ClientConfig, inputs: Inputs) -> Result<Client, ClientError> {
+        Ok(Client {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            session: config.session.ok_or(ClientError::NoSession)?,
+            inputs,
+            throttle: DEFAULT_THROTTLE,
+            last_request: None,
+            stamp: Some(env::temp_dir().join(STAMP_FILE)),
+        })
+    }
+
+    /// Change the minimum time between requests
+    pub fn with_throttle(self, throttle: Duration) -> Client {
+        Client { throttle, ..self }
+    }
+
+    /// Change where the time of the last request is shared with other runs
+    pub fn with_stamp(self, stamp: impl Into<PathBuf>) -> Client {
+        Client {
+            stamp: Some(stamp.into()),
+            ..self
+        }
+    }
+
+    /// Wait until enough time has passed since the last request, from this run or any other
+    fn wait_turn(&mut self) {
+        let saved = self.stamp.as_deref().and_then(read_stamp);
+        // A time in the future means the clock has changed, so it's no use for spacing requests
+        if let Some(since) = self.last_request.max(saved).and_then(|t| t.elapsed().ok()) {
+            if since < self.throttle {
+                thread::sleep(self.throttle - since);
+            }
+        }
+
+        let now = SystemTime::now();
+        self.last_request = Some(now);
+        if let Some(stamp) = &self.stamp {
+            // Not being able to save it only loses the spacing between runs
+            let _ = write_stamp(stamp, now);
+        }
+    }
+
+    fn send(
+        &mut self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, ClientError> {
+        self.wait_turn();
+        let url = request.url().to_string();
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response.into_string().map_err(|e| ClientError::Transport {
+                url,
+                reason: e.to_string(),
+            }),
+            Err(ureq::Error::Status(status, _)) => Err(ClientError::Status { url, status }),
+            Err(ureq::Error::Transport(err)) => Err(ClientError::Transport {
+                url,
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    /// Whether the input for a day has already been saved
+    pub fn is_cached(&self, year: u32, day: u32) -> bool {
+        self.inputs.path(year, day).exists()
+    }
+
+    /// The input for a day, downloading and saving it first if it isn't saved already
+    pub fn fetch_input(&mut self, year: u32, day: u32) -> Result<String, ClientError> {
+        if self.is_cached(year, day) {
+            return Ok(self.inputs.load(year, day)?);
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let text = self.send(self.agent.get(&url), None)?;
+
+        let path = self.inputs.path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| ClientError::Save(dir.to_path_buf(), e))?;
+        }
+        fs::write(&path, &text).map_err(|e| ClientError::Save(path, e))?;
+        Ok(input::normalise(&text))
+    }
+
+    /// Submit an answer, returning what the site made of it
+    pub fn submit(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &Answer,
+    ) -> Result<Verdict, ClientError> {
+        if answer.is_multiline() {
+            return Err(ClientError::Image);
+        }
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let answer = answer.to_string();
+        let html = self.send(
+            self.agent.post(&url),
+            Some(&[("level", &level), ("answer", &answer)]),
+        )?;
+        Verdict::parse(&html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    use tiny_http::{Method, Response, Server};
+
+    use super::*;
+
+    const SESSION: &str = "53616c7465645f5f";
+
+    fn page(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    /// A stand-in for the site on a local port, which records each request it gets as
+    /// `"METHOD URL BODY"`
+    fn serve(responses: HashMap<String, (u16, String)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let server_log = Arc::clone(&log);
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let authorised = request.headers().iter().any(|h| {
+                    h.field.equiv("Cookie") && h.value.as_str() == format!("session={}", SESSION)
+                });
+                let method = if *request.method() == Method::Post {
+                    "POST"
+                } else {
+                    "GET"
+                };
+                server_log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {} {}", method, request.url(), body));
+
+                let (status, text) = match responses.get(request.url()) {
+                    _ if !authorised => (400, "Puzzle inputs differ by user.".to_string()),
+                    Some(response) => response.clone(),
+                    None => (404, "Not found".to_string()),
+                };
+                request
+                    .respond(Response::from_string(text).with_status_code(status))
+                    .unwrap();
+            }
+        });
+        (base_url, log)
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_cli_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn client(base_url: &str, dir: &PathBuf) -> Client {
+        fs::create_dir_all(dir).unwrap();
+        let config = ClientConfig {
+            base_url: base_url.to_string(),
+            session: Some(SESSION.to_string()),
+        };
+        Client::new(config, Inputs::default().with_dir(dir))
+            .unwrap()
+            .with_throttle(Duration::ZERO)
+            .with_stamp(dir.join(STAMP_FILE))
+    }
+
+    #[test]
+    fn fetches_once() {
+        let responses = HashMap::from([(
+            "/2022/day/1/input".to_string(),
+            (200, "1000\r\n2000\r\n\r\n3000\r\n".to_string()),
+        )]);
+        let (base_url, log) = serve(responses);
+        let dir = scratch_dir("fetch");
+        let mut client = client(&base_url, &dir);
+
+        assert!(!client.is_cached(2022, 1));
+        assert_eq!(client.fetch_input(2022, 1).unwrap(), "1000\n2000\n\n3000");
+        assert!(client.is_cached(2022, 1));
+        assert!(dir.join("2022/day1.txt").exists());
+        assert_eq!(client.fetch_input(2022, 1).unwrap(), "1000\n2000\n\n3000");
+        assert_eq!(log.lock().unwrap().len(), 1);
+
+        assert!(matches!(
+            client.fetch_input(2022, 2),
+            Err(ClientError::Status { status: 404, .. })
+        ));
+        assert!(!client.is_cached(2022, 2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submits() {
+        let responses = HashMap::from([(
+            "/2022/day/7/answer".to_string(),
+            (
+                200,
+                page("That's the right answer! You are one gold star closer."),
+            ),
+        )]);
+        let (base_url, log) = serve(responses);
+        let dir = scratch_dir("submit");
+        let mut client = client(&base_url, &dir);
+
+        let verdict = client.submit(2022, 7, 2, &Answer::Integer(404_395));
+        assert_eq!(verdict.unwrap(), Verdict::Correct);
+        assert_eq!(
+            log.lock().unwrap()[..],
+            ["POST /2022/day/7/answer level=2&answer=404395"]
+        );
+
+        let image = Answer::Image(vec!["#..#".to_string(), "####".to_string()]);
+        assert!(matches!(
+            client.submit(2022, 10, 2, &image),
+            Err(ClientError::Image)
+        ));
+        assert_eq!(log.lock().unwrap().len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn throttles() {
+        let responses = HashMap::from([
+            ("/2022/day/1/input".to_string(), (200, "1".to_string())),
+            ("/2022/day/2/input".to_string(), (200, "2".to_string())),
+            ("/2022/day/3/input".to_string(), (200, "3".to_string())),
+        ]);
+        let (base_url, _) = serve(responses);
+        let dir = scratch_dir("throttle");
+        let throttle = Duration::from_millis(200);
+        let mut first = client(&base_url, &dir).with_throttle(throttle);
+
+        let start = Instant::now();
+        first.fetch_input(2022, 1).unwrap();
+        first.fetch_input(2022, 2).unwrap();
+        assert!(start.elapsed() >= throttle);
+
+        // A new client, as in another run, waits for the last request too
+        let mut second = client(&base_url, &dir).with_throttle(throttle);
+        second.fetch_input(2022, 3).unwrap();
+        assert!(start.elapsed() >= throttle * 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn needs_session() {
+        let config = ClientConfig::default();
+        assert!(matches!(
+            Client::new(config, Inputs::default()),
+            Err(ClientError::NoSession)
+        ));
+
+        let config = ClientConfig::from_config(
+            "[client]\nbase_url = \"http://localhost:8080\"\nsession = \"abc\"",
+        )
+        .unwrap();
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert!(ClientConfig::from_config("[client]\ncookie = \"abc\"").is_err());
+    }
+
+    #[test]
+    fn verdicts() {
+        let verdict = |text: &str| Verdict::parse(&page(text)).unwrap();
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict("That's not the right answer. If you're stuck, make sure you're using the full input data"),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have <span>1m 5s</span> left to wait. <a href=\"/2022/day/7\">[Return to Day 7]</a>"),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level.  Did you already complete it?"),
+            Verdict::WrongLevel
+        );
+        assert!(matches!(
+            Verdict::parse("<html>Something else</html>"),
+            Err(ClientError::UnexpectedResponse(_))
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+//! Runs the solutions from every year's crate through one interface
+
+pub mod bench;
+pub mod client;
+pub mod registry;
+pub mod run;
+pub mod scaffold;
@@ -0,0 +1,517 @@
+use std::collections::hash_map::{Entry, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_helpers::answer::Answer;
+use aoc_helpers::answers::{Answers, Outcome};
+use aoc_helpers::input::{self, InputError, Inputs};
+use clap::{Parser, Subcommand};
+
+use aoc_cli::bench;
+use aoc_cli::client::{Client, ClientConfig, ClientError, Verdict};
+use aoc_cli::registry::{self, Solver};
+use aoc_cli::run;
+use aoc_cli::scaffold;
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions from every year")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solutions, by default every one that's registered
+    Run {
+        #[arg(long)]
+        year: Option<u32>,
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long)]
+        part: Option<u32>,
+        /// Input to use instead of the day's usual input file
+        #[arg(long, requires_all = ["year", "day"])]
+        input: Option<PathBuf>,
+        /// Input profile to use instead of the configured one
+        #[arg(long, conflicts_with = "input")]
+        profile: Option<String>,
+    },
+    /// Check solutions against the answers recorded in `answers/YEAR.toml`
+    Check {
+        #[arg(long)]
+        year: Option<u32>,
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long)]
+        part: Option<u32>,
+        /// Input profile to use instead of the configured one
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Time solutions, saving the timings as JSON or comparing them with saved ones
+    Bench {
+        #[arg(long)]
+        year: Option<u32>,
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long)]
+        part: Option<u32>,
+        /// How many times to run each solution, keeping the median time
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+        /// File to save the timings to
+        #[arg(long)]
+        save: Option<PathBuf>,
+        /// Timings saved earlier, to flag solutions which are now slower
+        #[arg(long)]
+        compare: Option<PathBuf>,
+        /// Percentage slowdown over the saved timings which counts as a regression
+        #[arg(long, default_value_t = 10.0, requires = "compare")]
+        threshold: f64,
+        /// Input profile to use instead of the configured one
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Download inputs which haven't been saved yet, by default for every day with a solution
+    Fetch {
+        #[arg(long)]
+        year: u32,
+        #[arg(long)]
+        day: Option<u32>,
+        /// Input profile to save to instead of the configured one
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Submit an answer, by default the one given by the day's solution
+    Submit {
+        #[arg(long)]
+        year: u32,
+        #[arg(long)]
+        day: u32,
+        #[arg(long)]
+        part: u32,
+        answer: Option<String>,
+    },
+    /// Start a new day from a template, creating its year's crate if there isn't one yet
+    New {
+        #[arg(long)]
+        year: u32,
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// List the registered solutions
+    List {
+        #[arg(long)]
+        year: Option<u32>,
+    },
+}
+
+#[derive(Default)]
+struct Summary {
+    solved: usize,
+    failed: usize,
+    skipped: usize,
+    time: Duration,
+}
+
+fn run_solver(solver: &Solver, input: &str, summary: &mut Summary) {
+    match run::run(solver, input) {
+        Ok((answer, timings)) => {
+            summary.solved += 1;
+            summary.time += timings.total();
+            if let Answer::Image(lines) = answer {
+                println!("{} [{}]:", solver, timings);
+                for line in lines {
+                    println!("    {}", line);
+                }
+            } else {
+                println!("{}: {} [{}]", solver, answer, timings);
+            }
+        }
+        Err(err) => {
+            summary.failed += 1;
+            println!("{}: FAILED, {}", solver, err);
+        }
+    }
+}
+
+/// Where inputs are configured to be, with the profile overridden if one's given
+fn inputs(profile: Option<String>) -> Result<Inputs, InputError> {
+    let inputs = Inputs::from_env()?;
+    Ok(match profile {
+        Some(profile) => inputs.with_profile(profile),
+        None => inputs,
+    })
+}
+
+fn run(
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+    profile: Option<String>,
+) -> bool {
+    let solvers = registry::find(year, day, part);
+    if solvers.is_empty() {
+        eprintln!("No solutions match");
+        return false;
+    }
+
+    let inputs = match inputs(profile) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    // An input given explicitly has to be there
+    let given = match input.map(input::read).transpose() {
+        Ok(given) => given,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    // Our own reports are enough, without the default hook's message and backtrace note
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut summary = Summary::default();
+    for solver in solvers {
+        let text = match &given {
+            Some(text) => Ok(text.clone()),
+            None => inputs.load(solver.year, solver.day),
+        };
+        match text {
+            Ok(text) => run_solver(solver, &text, &mut summary),
+            Err(err) => {
+                summary.skipped += 1;
+                println!("{}: skipped, {}", solver, err);
+            }
+        }
+    }
+
+    println!(
+        "\n{} solved, {} failed, {} skipped in {:?}",
+        summary.solved, summary.failed, summary.skipped, summary.time
+    );
+    summary.failed == 0
+}
+
+#[derive(Default)]
+struct CheckSummary {
+    correct: usize,
+    wrong: usize,
+    failed: usize,
+    unsolved: usize,
+    missing: usize,
+}
+
+fn check(year: Option<u32>, day: Option<u32>, part: Option<u32>, profile: Option<String>) -> bool {
+    let solvers = registry::find(year, day, part);
+    if solvers.is_empty() {
+        eprintln!("No solutions match");
+        return false;
+    }
+
+    let inputs = match inputs(profile) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut answers = HashMap::new();
+    let mut summary = CheckSummary::default();
+    for solver in solvers {
+        let year_answers = match answers.entry(solver.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match Answers::load(solver.year, inputs.profile()) {
+                Ok(loaded) => entry.insert(loaded),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return false;
+                }
+            },
+        };
+
+        let outcome = match inputs.load(solver.year, solver.day) {
+            Ok(text) => run::check(solver, &text, year_answers).map_err(|e| e.to_string()),
+            Err(InputError::Missing { path, .. }) => Ok(Outcome::MissingInput(path)),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match outcome {
+            Ok(Outcome::Correct) => summary.correct += 1,
+            Ok(Outcome::Unsolved) => summary.unsolved += 1,
+            Ok(Outcome::MissingInput(_) | Outcome::MissingAnswer) => summary.missing += 1,
+            Ok(Outcome::Wrong { .. }) => summary.wrong += 1,
+            Err(_) => summary.failed += 1,
+        }
+        match outcome {
+            Ok(outcome) => println!("{}: {}", solver, outcome),
+            Err(err) => println!("{}: FAILED, {}", solver, err),
+        }
+    }
+
+    println!(
+        "\n{} correct, {} wrong, {} failed, {} unsolved, {} missing an input or answer",
+        summary.correct, summary.wrong, summary.failed, summary.unsolved, summary.missing
+    );
+    summary.wrong == 0 && summary.failed == 0
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bench(
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    samples: usize,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    threshold: f64,
+    profile: Option<String>,
+) -> bool {
+    let solvers = registry::find(year, day, part);
+    if solvers.is_empty() {
+        eprintln!("No solutions match");
+        return false;
+    }
+
+    let inputs = match inputs(profile) {
+        Ok(inputs) => inputs,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+    // Read the baseline first, so a typo doesn't waste a whole run
+    let baseline = match compare.as_deref().map(bench::Summary::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mut summary = bench::Summary::default();
+    for solver in solvers {
+        let measured = inputs
+            .load(solver.year, solver.day)
+            .map_err(|e| e.to_string())
+            .and_then(|text| bench::measure(solver, &text, samples).map_err(|e| e.to_string()));
+        match measured {
+            Ok(measurement) => {
+                println!(
+                    "{}: parse: {:?}, run: {:?}",
+                    solver,
+                    measurement.parse(),
+                    measurement.run()
+                );
+                summary.measurements.push(measurement);
+            }
+            Err(err) => println!("{}: skipped, {}", solver, err),
+        }
+    }
+
+    if let Some(path) = save {
+        if let Err(err) = summary.save(&path) {
+            eprintln!("{}", err);
+            return false;
+        }
+        println!(
+            "\nSaved {} timings to {}",
+            summary.measurements.len(),
+            path.display()
+        );
+    }
+
+    let Some(baseline) = baseline else {
+        return true;
+    };
+    let regressions = bench::compare(&baseline, &summary, threshold / 100.0);
+    println!(
+        "\n{} regressions over {}% against {}",
+        regressions.len(),
+        threshold,
+        compare.unwrap().display()
+    );
+    for regression in &regressions {
+        println!("{}", regression);
+    }
+    regressions.is_empty()
+}
+
+/// A client for the configured site, saving inputs for the given profile
+fn client(profile: Option<String>) -> Result<Client, ClientError> {
+    Client::new(ClientConfig::from_env()?, inputs(profile)?)
+}
+
+fn fetch(year: u32, day: Option<u32>, profile: Option<String>) -> bool {
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => {
+            let mut days: Vec<u32> = registry::find(Some(year), None, None)
+                .iter()
+                .map(|solver| solver.day)
+                .collect();
+            days.dedup();
+            days
+        }
+    };
+    if days.is_empty() {
+        eprintln!("No solutions for {}, pass --day to fetch one anyway", year);
+        return false;
+    }
+
+    let mut client = match client(profile) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for day in days {
+        if client.is_cached(year, day) {
+            println!("{} day {}: already saved", year, day);
+            continue;
+        }
+        match client.fetch_input(year, day) {
+            Ok(text) => println!("{} day {}: saved {} lines", year, day, text.lines().count()),
+            Err(err) => {
+                ok = false;
+                println!("{} day {}: FAILED, {}", year, day, err);
+            }
+        }
+    }
+    ok
+}
+
+/// The answer the day's solution gives, using the first registered solver for the part
+fn solve(year: u32, day: u32, part: u32) -> Result<Answer, String> {
+    let solver = *registry::find(Some(year), Some(day), Some(part))
+        .first()
+        .ok_or_else(|| format!("No solution for {} day {} part {}", year, day, part))?;
+    let input = input::load(year, day).map_err(|e| e.to_string())?;
+    let (answer, _) = run::run(solver, &input).map_err(|e| format!("{}: {}", solver, e))?;
+    println!("{}: {}", solver, answer);
+    Ok(answer)
+}
+
+fn submit(year: u32, day: u32, part: u32, answer: Option<String>) -> bool {
+    let answer = match answer {
+        Some(answer) => Ok(Answer::parse(&answer)),
+        None => solve(year, day, part),
+    };
+    let result = answer
+        .and_then(|answer| {
+            client(None)
+                .map(|client| (client, answer))
+                .map_err(|e| e.to_string())
+        })
+        .and_then(|(mut client, answer)| {
+            client
+                .submit(year, day, part, &answer)
+                .map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(verdict) => {
+            println!("{} day {} part {}: {}", year, day, part, verdict);
+            verdict == Verdict::Correct
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            profile,
+        } => {
+            if !run(year, day, part, input, profile) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Check {
+            year,
+            day,
+            part,
+            profile,
+        } => {
+            if !check(year, day, part, profile) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Bench {
+            year,
+            day,
+            part,
+            samples,
+            save,
+            compare,
+            threshold,
+            profile,
+        } => {
+            if !bench(year, day, part, samples, save, compare, threshold, profile) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Fetch { year, day, profile } => {
+            if !fetch(year, day, profile) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+        } => {
+            if !submit(year, day, part, answer) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::New { year, day } => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("aoc_cli is inside the repository");
+            match scaffold::new_day(root, year, day) {
+                Ok(changed) => {
+                    for path in changed {
+                        println!(
+                            "wrote {}",
+                            path.strip_prefix(root).unwrap_or(&path).display()
+                        );
+                    }
+                }
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::List { year } => {
+            for solver in registry::find(year, None, None) {
+                println!("{}", solver);
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
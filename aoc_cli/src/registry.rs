@@ -0,0 +1,84 @@
+use std::error::Error;
+use std::fmt;
+
+use aoc_runner::{ArcStr, Runner};
+
+/// Builds a solver's runner from its input, running the generator if it has one
+pub type Factory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A solution registered with `#[aoc(dayN, partK)]` in one of the year crates
+#[derive(Copy, Clone)]
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// Name given to alternative solutions, as in `#[aoc(day1, part1, Bytes)]`
+    pub name: Option<&'static str>,
+    pub factory: Factory,
+}
+
+impl fmt::Debug for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Solver")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("part", &self.part)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl fmt::Display for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " ({})", name)?;
+        }
+        Ok(())
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/solvers.rs"));
+
+/// Every registered solver matching the filters, in order of year, day and part
+pub fn find(year: Option<u32>, day: Option<u32>, part: Option<u32>) -> Vec<&'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(|s| year.is_none_or(|y| s.year == y))
+        .filter(|s| day.is_none_or(|d| s.day == d))
+        .filter(|s| part.is_none_or(|p| s.part == p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_year_registered() {
+        for year in 2020..=2025 {
+            assert!(!find(Some(year), None, None).is_empty(), "{} missing", year);
+        }
+    }
+
+    #[test]
+    fn sorted_and_unique() {
+        let keys: Vec<_> = SOLVERS
+            .iter()
+            .map(|s| (s.year, s.day, s.part, s.name))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn filters() {
+        let found = find(Some(2022), Some(10), None);
+        assert_eq!(
+            found.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            ["2022 day 10 part 1", "2022 day 10 part 2"]
+        );
+    }
+}
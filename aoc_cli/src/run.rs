@@ -0,0 +1,127 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use aoc_helpers::answer::Answer;
+use aoc_helpers::answers::{self, Answers, Outcome};
+use aoc_runner::ArcStr;
+
+use crate::registry::Solver;
+
+/// How long each stage of a solver took
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Timings {
+    /// Running the generator, if there is one
+    pub parse: Duration,
+    pub run: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.run
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse: {:?}, run: {:?}", self.parse, self.run)
+    }
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    /// The generator returned an error
+    Generator(String),
+    /// The solver returned an error
+    Solver(String),
+    /// Something panicked, including unfinished parts that are still `todo!()`
+    Panic(String),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Generator(e) => write!(f, "generator failed: {}", e),
+            Self::Solver(e) => write!(f, "solver failed: {}", e),
+            Self::Panic(msg) => write!(f, "panicked: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Run a solver on `input`, timing the generator and the solution separately
+///
+/// Trailing newlines are stripped from the input first, as `cargo aoc` does.
+/// Solvers can return any type which can be displayed, so the answer is interpreted from how it's
+/// printed.
+pub fn run(solver: &Solver, input: &str) -> Result<(Answer, Timings), RunError> {
+    let input = ArcStr::from(input);
+    let caught = panic::catch_unwind(AssertUnwindSafe(|| {
+        let start = Instant::now();
+        let runner = (solver.factory)(input).map_err(|e| RunError::Generator(e.to_string()))?;
+        let parsed = Instant::now();
+        let answer = runner
+            .try_run()
+            .map_err(|e| RunError::Solver(e.to_string()))?;
+        let timings = Timings {
+            parse: parsed - start,
+            run: parsed.elapsed(),
+        };
+        Ok((Answer::parse(&answer.to_string()), timings))
+    }));
+    caught.unwrap_or_else(|payload| Err(RunError::Panic(panic_message(payload))))
+}
+
+/// Run a solver on `input` and compare its answer with the recorded one
+///
+/// Parts which are still `todo!()` are unsolved rather than errors.
+pub fn check(solver: &Solver, input: &str, answers: &Answers) -> Result<Outcome, RunError> {
+    match run(solver, input) {
+        Ok((answer, _)) => Ok(answers.compare(solver.day, solver.part, answer)),
+        Err(RunError::Panic(msg)) if answers::is_unsolved_panic(&msg) => Ok(Outcome::Unsolved),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find;
+
+    const DEPTHS: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    #[test]
+    fn answers() {
+        let solver = find(Some(2021), Some(1), Some(1))[0];
+        let (answer, _) = run(solver, DEPTHS).unwrap();
+        assert_eq!(answer, Answer::Integer(7));
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let solver = find(Some(2021), Some(1), Some(1))[0];
+        assert!(matches!(run(solver, "deep"), Err(RunError::Panic(_))));
+    }
+
+    #[test]
+    fn checks() {
+        let solver = find(Some(2021), Some(1), Some(1))[0];
+        let answers = Answers::parse("[day1]\npart1 = 7").unwrap();
+        assert_eq!(check(solver, DEPTHS, &answers).unwrap(), Outcome::Correct);
+        let answers = Answers::parse("[day1]\npart1 = 8").unwrap();
+        assert!(check(solver, DEPTHS, &answers).unwrap().is_failure());
+        assert_eq!(
+            check(solver, DEPTHS, &Answers::default()).unwrap(),
+            Outcome::MissingAnswer
+        );
+    }
+}
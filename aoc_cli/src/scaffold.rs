@@ -0,0 +1,474 @@
+//! Creating new days, and new year crates to put them in
+//!
+//! A day is written from a template in `templates/`, using whichever of nom or winnow its year
+//! already depends on, or neither if it depends on neither, and declared in the year's `lib.rs`
+//! alongside the other days in order. Its tests check its answers with the year's `check_answer`,
+//! which is added to `lib.rs` if it isn't there yet.
+//! A year crate that doesn't exist yet is created with the `aoc_lib!` boilerplate, and added to
+//! this crate's dependencies so that its solutions are registered.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DAY_NOM: &str = include_str!("../templates/day_nom.rs.tmpl");
+const DAY_WINNOW: &str = include_str!("../templates/day_winnow.rs.tmpl");
+const DAY_PLAIN: &str = include_str!("../templates/day_plain.rs.tmpl");
+const YEAR_LIB: &str = include_str!("../templates/lib.rs.tmpl");
+const YEAR_MANIFEST: &str = include_str!("../templates/Cargo.toml.tmpl");
+const CHECK_ANSWER: &str = include_str!("../templates/check_answer.rs.tmpl");
+
+const HELPERS_DEPENDENCY: &str = "aoc_helpers = { path = \"../aoc_helpers\" }";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day's module is already there
+    Exists(PathBuf),
+    /// The year's `lib.rs` has no `aoc_lib!` to declare the day before
+    NoAocLib(PathBuf),
+    /// A manifest has no `[dependencies]` table to add to
+    NoDependencies(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::NoAocLib(path) => write!(f, "{} has no aoc_lib! invocation", path.display()),
+            Self::NoDependencies(path) => {
+                write!(f, "{} has no [dependencies] table", path.display())
+            }
+            Self::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// Which parser combinator library a year's solutions use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParserLib {
+    Nom,
+    Winnow,
+}
+
+impl ParserLib {
+    /// The library a year's manifest depends on, if it depends on either
+    pub fn detect(manifest: &str) -> Option<ParserLib> {
+        let depends_on = |name: &str| {
+            manifest
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .any(|(key, _)| key.trim() == name)
+        };
+        if depends_on("winnow") {
+            Some(ParserLib::Winnow)
+        } else if depends_on("nom") {
+            Some(ParserLib::Nom)
+        } else {
+            None
+        }
+    }
+
+    fn dependency(self) -> &'static str {
+        match self {
+            ParserLib::Nom => "nom = \"7\"",
+            ParserLib::Winnow => "winnow = \"0.7\"",
+        }
+    }
+}
+
+fn render(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{{ year }}", &year.to_string())
+        .replace("{{ day }}", &day.to_string())
+}
+
+/// The source of a new day, which imports the `aoc` attributes itself unless the crate uses
+/// `#[macro_use]` for them
+///
+/// Without a parser library, the generator just splits the input into lines.
+pub fn day_source(year: u32, day: u32, parser: Option<ParserLib>, macro_use: bool) -> String {
+    let template = match parser {
+        Some(ParserLib::Nom) => DAY_NOM,
+        Some(ParserLib::Winnow) => DAY_WINNOW,
+        None => DAY_PLAIN,
+    };
+    let source = render(template, year, day);
+    if macro_use {
+        source
+    } else {
+        format!(
+            "use aoc_runner_derive::{{aoc, aoc_generator}};\n\n{}",
+            source
+        )
+    }
+}
+
+/// The module of a `mod dayN;` or `pub mod dayN;` line
+fn declared_module(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    let module = line.strip_prefix("mod ")?.strip_suffix(';')?;
+    module.strip_prefix("day")?.parse::<u32>().ok()?;
+    Some(module)
+}
+
+fn declared_day(line: &str) -> Option<u32> {
+    declared_module(line)?[3..].parse().ok()
+}
+
+/// The name of a day's module, zero-padded unless the year's existing days aren't
+pub fn module_name(lib: &str, day: u32) -> String {
+    let unpadded = lib
+        .lines()
+        .filter_map(declared_module)
+        .any(|module| module.len() == "day1".len());
+    if unpadded {
+        format!("day{}", day)
+    } else {
+        format!("day{:02}", day)
+    }
+}
+
+/// Declare `module` in a `lib.rs`, keeping the days in order
+///
+/// The first run of day declarations is re-sorted by day, and the new one takes the same
+/// visibility as the others. With no days declared yet, it goes just before `aoc_lib!`.
+/// Returns `None` if there are no days and no `aoc_lib!` either.
+pub fn register(lib: &str, module: &str) -> Option<String> {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let day: u32 = module.strip_prefix("day")?.parse().ok()?;
+
+    let declaration;
+    match lines.iter().position(|line| declared_day(line).is_some()) {
+        Some(start) => {
+            let end = start
+                + lines[start..]
+                    .iter()
+                    .take_while(|line| declared_day(line).is_some())
+                    .count();
+            if lines[start..end]
+                .iter()
+                .any(|&line| declared_day(line) == Some(day))
+            {
+                return Some(lib.to_string());
+            }
+            let visibility = if lines[start].trim().starts_with("pub ") {
+                "pub "
+            } else {
+                ""
+            };
+            declaration = format!("{}mod {};", visibility, module);
+            lines.insert(end, &declaration);
+            lines[start..=end].sort_by_key(|line| declared_day(line));
+        }
+        None => {
+            let at = lines
+                .iter()
+                .position(|line| line.trim_start().starts_with("aoc_lib!"))?;
+            declaration = format!("mod {};", module);
+            lines.splice(at..at, [declaration.as_str(), ""]);
+        }
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Some(out)
+}
+
+/// Add the `check_answer` helper used by the days' tests to the end of a `lib.rs`, unless it's
+/// already defined
+pub fn add_check_answer(lib: &str, year: u32) -> String {
+    if lib.contains("fn check_answer") {
+        return lib.to_string();
+    }
+    format!(
+        "{}
+
+{}",
+        lib.trim_end(),
+        render(CHECK_ANSWER, year, 0)
+    )
+}
+
+/// Add a line to a manifest's `[dependencies]` table, unless there's already a dependency with the
+/// same name
+///
+/// Path dependencies go at the end of the table, and others after the last dependency that isn't
+/// a path, since the crates list them in two groups.
+pub fn add_dependency(manifest: &str, dependency: &str) -> Option<String> {
+    let name = dependency.split('=').next()?.trim();
+    if manifest
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, _)| key.trim() == name)
+    {
+        return Some(manifest.to_string());
+    }
+
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let header = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")?;
+    let is_path = |line: &str| line.contains("path =");
+    let last = header
+        + lines[header + 1..]
+            .iter()
+            .take_while(|line| !line.trim_start().starts_with('['))
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter(|(_, line)| is_path(dependency) || !is_path(line))
+            .map(|(i, _)| i + 1)
+            .last()
+            .unwrap_or(0);
+    // Keep path dependencies apart from the rest
+    if is_path(dependency) && last > header && !is_path(lines[last]) {
+        lines.insert(last + 1, "");
+        lines.insert(last + 2, dependency);
+    } else {
+        lines.insert(last + 1, dependency);
+    }
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Some(out)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| ScaffoldError::Io(dir.to_path_buf(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Rewrite a file with `edit`, reporting whether anything changed
+fn update(
+    path: &Path,
+    edit: impl FnOnce(&str) -> Option<String>,
+    error: impl FnOnce(PathBuf) -> ScaffoldError,
+) -> Result<bool, ScaffoldError> {
+    let old = read(path)?;
+    let new = edit(&old).ok_or_else(|| error(path.to_path_buf()))?;
+    if new == old {
+        return Ok(false);
+    }
+    write(path, &new)?;
+    Ok(true)
+}
+
+/// Create the module for a day, and its year crate if needed, under the repository at `root`
+///
+/// Returns the files which were created or changed.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let crate_dir = root.join(format!("aoc{}", year));
+    let manifest_path = crate_dir.join("Cargo.toml");
+    let lib_path = crate_dir.join("src").join("lib.rs");
+    let mut changed = Vec::new();
+
+    if !manifest_path.exists() {
+        write(&manifest_path, &render(YEAR_MANIFEST, year, day))?;
+        write(&lib_path, &render(YEAR_LIB, year, day))?;
+        changed.extend([manifest_path.clone(), lib_path.clone()]);
+
+        let cli_manifest = root.join("aoc_cli").join("Cargo.toml");
+        let dependency = format!("aoc{year} = {{ path = \"../aoc{year}\" }}");
+        if update(
+            &cli_manifest,
+            |text| add_dependency(text, &dependency),
+            ScaffoldError::NoDependencies,
+        )? {
+            changed.push(cli_manifest);
+        }
+    }
+
+    let lib = read(&lib_path)?;
+    let module = module_name(&lib, day);
+    let day_path = crate_dir.join("src").join(format!("{}.rs", module));
+    if day_path.exists() {
+        return Err(ScaffoldError::Exists(day_path));
+    }
+
+    // New years get winnow from their manifest template, and older years without a parser library
+    // are left without one
+    let parser = ParserLib::detect(&read(&manifest_path)?);
+    let dependencies = parser.map(ParserLib::dependency).into_iter();
+    for dependency in dependencies.chain([HELPERS_DEPENDENCY]) {
+        if update(
+            &manifest_path,
+            |text| add_dependency(text, dependency),
+            ScaffoldError::NoDependencies,
+        )? && !changed.contains(&manifest_path)
+        {
+            changed.push(manifest_path.clone());
+        }
+    }
+
+    let macro_use = lib.contains("#[macro_use]");
+    write(&day_path, &day_source(year, day, parser, macro_use))?;
+    changed.push(day_path);
+
+    if update(
+        &lib_path,
+        |text| register(&add_check_answer(text, year), &module),
+        ScaffoldError::NoAocLib,
+    )? && !changed.contains(&lib_path)
+    {
+        changed.push(lib_path);
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn detects_parsers() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let detect = |year: u32| {
+            let manifest = fs::read_to_string(root.join(format!("aoc{}/Cargo.toml", year)));
+            ParserLib::detect(&manifest.unwrap())
+        };
+        assert_eq!(detect(2021), None);
+        assert_eq!(detect(2022), Some(ParserLib::Nom));
+        assert_eq!(detect(2023), Some(ParserLib::Nom));
+        assert_eq!(detect(2024), Some(ParserLib::Winnow));
+        assert_eq!(detect(2025), Some(ParserLib::Winnow));
+    }
+
+    #[test]
+    fn registers_in_order() {
+        let lib =
+            "pub mod iter_ext;\n\nmod day01;\nmod day10;\nmod day02;\naoc_lib! { year = 2024 }\n";
+        assert_eq!(
+            register(lib, "day05").unwrap(),
+            "pub mod iter_ext;\n\nmod day01;\nmod day02;\nmod day05;\nmod day10;\naoc_lib! { year = 2024 }\n"
+        );
+        assert_eq!(register(lib, "day10").unwrap(), lib);
+
+        let lib = "pub mod day1;\npub mod day10;\npub mod day2;\n\npub mod bits;\n";
+        assert_eq!(
+            register(lib, "day3").unwrap(),
+            "pub mod day1;\npub mod day2;\npub mod day3;\npub mod day10;\n\npub mod bits;\n"
+        );
+
+        let lib = "#[macro_use]\nextern crate aoc_runner_derive;\n\naoc_lib! { year = 2026 }\n";
+        assert_eq!(
+            register(lib, "day01").unwrap(),
+            "#[macro_use]\nextern crate aoc_runner_derive;\n\nmod day01;\n\naoc_lib! { year = 2026 }\n"
+        );
+        assert_eq!(register("", "day01"), None);
+    }
+
+    #[test]
+    fn module_names() {
+        assert_eq!(module_name("mod day01;\nmod day12;", 3), "day03");
+        assert_eq!(module_name("pub mod day1;\npub mod day12;", 3), "day3");
+        assert_eq!(module_name("", 3), "day03");
+    }
+
+    #[test]
+    fn adds_dependencies() {
+        let manifest =
+            "[dependencies]\naoc-runner = \"0.3\"\n\n[dev-dependencies]\ntest-case = \"3\"\n";
+        assert_eq!(
+            add_dependency(manifest, "winnow = \"0.7\"").unwrap(),
+            "[dependencies]\naoc-runner = \"0.3\"\nwinnow = \"0.7\"\n\n[dev-dependencies]\ntest-case = \"3\"\n"
+        );
+        let manifest =
+            "[dependencies]\naoc-runner = \"0.3\"\n\naoc_helpers = { path = \"../aoc_helpers\" }\n";
+        assert_eq!(
+            add_dependency(manifest, "winnow = \"0.7\"").unwrap(),
+            "[dependencies]\naoc-runner = \"0.3\"\nwinnow = \"0.7\"\n\naoc_helpers = { path = \"../aoc_helpers\" }\n"
+        );
+        assert_eq!(
+            add_dependency(manifest, "aoc-runner = \"0.3\"").unwrap(),
+            manifest
+        );
+        assert_eq!(
+            add_dependency("[dependencies]\nwinnow = \"0.7\"\n", HELPERS_DEPENDENCY).unwrap(),
+            "[dependencies]\nwinnow = \"0.7\"\n\naoc_helpers = { path = \"../aoc_helpers\" }\n"
+        );
+        assert_eq!(add_dependency("[package]\n", "winnow = \"0.7\""), None);
+    }
+
+    #[test]
+    fn creates_years() {
+        let root = env::temp_dir().join(format!("aoc_cli_scaffold_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write(
+            &root.join("aoc_cli/Cargo.toml"),
+            "[dependencies]\naoc2024 = { path = \"../aoc2024\" }\n",
+        )
+        .unwrap();
+
+        let changed = new_day(&root, 2030, 5).unwrap();
+        assert_eq!(changed.len(), 4);
+        let lib = fs::read_to_string(root.join("aoc2030/src/lib.rs")).unwrap();
+        assert!(lib.contains("mod day05;\n\naoc_lib! { year = 2030 }"));
+        let day = fs::read_to_string(root.join("aoc2030/src/day05.rs")).unwrap();
+        assert!(day.contains("#[aoc(day5, part2)]"));
+        assert!(day.contains("use winnow::Parser;"));
+        assert!(day.contains("crate::check_answer(5, 1,"));
+        assert_eq!(lib.matches("fn check_answer").count(), 1);
+        assert!(fs::read_to_string(root.join("aoc_cli/Cargo.toml"))
+            .unwrap()
+            .contains("aoc2030 = { path = \"../aoc2030\" }"));
+
+        let changed = new_day(&root, 2030, 2).unwrap();
+        assert_eq!(
+            changed,
+            [
+                root.join("aoc2030/src/day02.rs"),
+                root.join("aoc2030/src/lib.rs")
+            ]
+        );
+        assert!(fs::read_to_string(root.join("aoc2030/src/lib.rs"))
+            .unwrap()
+            .contains("mod day02;\nmod day05;\n"));
+        assert!(matches!(
+            new_day(&root, 2030, 5),
+            Err(ScaffoldError::Exists(_))
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_years_without_parsers() {
+        let root = env::temp_dir().join(format!("aoc_cli_scaffold_plain_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let manifest = "[dependencies]\naoc-runner = \"0.3\"\n";
+        write(&root.join("aoc2031/Cargo.toml"), manifest).unwrap();
+        write(
+            &root.join("aoc2031/src/lib.rs"),
+            "#[macro_use]\nextern crate aoc_runner_derive;\n\npub mod day1;\n\naoc_lib! { year = 2031 }\n",
+        )
+        .unwrap();
+
+        new_day(&root, 2031, 2).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("aoc2031/Cargo.toml")).unwrap(),
+            format!("{}\n{}\n", manifest, HELPERS_DEPENDENCY)
+        );
+        let day = fs::read_to_string(root.join("aoc2031/src/day2.rs")).unwrap();
+        assert!(day.starts_with("#[aoc_generator(day2)]"));
+        assert!(!day.contains("winnow") && !day.contains("nom"));
+        assert_eq!(
+            fs::read_to_string(root.join("aoc2031/src/lib.rs")).unwrap(),
+            format!(
+                "#[macro_use]\nextern crate aoc_runner_derive;\n\npub mod day1;\npub mod day2;\n\naoc_lib! {{ year = 2031 }}\n\n{}",
+                render(CHECK_ANSWER, 2031, 2)
+            )
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
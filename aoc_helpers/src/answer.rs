@@ -0,0 +1,181 @@
+//! A common type for puzzle answers, whatever type the solution actually returns.
+//!
+//! Most answers are integers of some width or other, but a few are words, and some are images
+//! drawn out in `#` characters which have to be read by eye.
+//! Converting them all to an [`Answer`] means they can be printed, compared and stored the same
+//! way.
+
+use std::fmt;
+
+/// A puzzle answer
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// An integer of any width
+    ///
+    /// Unsigned values above `i128::MAX` can't be stored, but no answer gets anywhere near that.
+    Integer(i128),
+    /// A single line of text
+    Text(String),
+    /// A picture over several lines, with no blank lines at the start or end
+    Image(Vec<String>),
+}
+
+impl Answer {
+    /// Interpret the printed form of an answer
+    ///
+    /// Anything printed the way an integer would be is one, anything over multiple lines is an
+    /// image, and anything else is text. Text such as `0123` which only parses as an integer
+    /// stays text, so that it's still printed the same way.
+    #[must_use]
+    pub fn parse(s: &str) -> Answer {
+        let trimmed = s.trim();
+        if let Ok(n) = trimmed.parse::<i128>() {
+            if n.to_string() == trimmed {
+                return Answer::Integer(n);
+            }
+        }
+
+        let lines: Vec<&str> = s.lines().collect();
+        let is_blank = |line: &&str| line.trim().is_empty();
+        let start = lines.iter().position(|l| !is_blank(l)).unwrap_or(0);
+        let end = lines
+            .iter()
+            .rposition(|l| !is_blank(l))
+            .map_or(0, |i| i + 1);
+        match &lines[start..end.max(start)] {
+            [] => Answer::Text(String::new()),
+            [line] => Answer::Text((*line).to_string()),
+            image => Answer::Image(image.iter().map(|l| l.trim_end().to_string()).collect()),
+        }
+    }
+
+    /// The integer value, if this is one
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Whether the answer takes more than one line to print
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Image(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Image(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Answer {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Answer {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    /// # Panics
+    /// If `n` is above `i128::MAX`
+    fn from(n: u128) -> Answer {
+        Answer::Integer(i128::try_from(n).expect("answer too large"))
+    }
+}
+
+impl From<&str> for Answer {
+    /// The same as [`Answer::parse`], so that a solution returning text gets the same answer as
+    /// the runner makes from printing it
+    fn from(s: &str) -> Answer {
+        Answer::parse(s)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::from(s.as_str())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Answer {
+        Answer::Image(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(Answer::from(5_u8), Answer::Integer(5));
+        assert_eq!(Answer::from(-3_isize), Answer::Integer(-3));
+        assert_eq!(Answer::from(5_usize), Answer::from(5_i32));
+        assert_eq!(
+            Answer::from(u128::from(u64::MAX) + 1).to_string(),
+            "18446744073709551616"
+        );
+        assert_eq!(
+            Answer::from(660_797_830_937_u64).as_integer(),
+            Some(660_797_830_937)
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Answer::parse("1444896\n"), Answer::Integer(1_444_896));
+        assert_eq!(Answer::parse("-12"), Answer::Integer(-12));
+        assert_eq!(
+            Answer::parse("3,6,3,7,0"),
+            Answer::Text("3,6,3,7,0".to_string())
+        );
+
+        let image = Answer::parse("\n#..#  \n####\n\n");
+        assert_eq!(
+            image,
+            Answer::Image(vec!["#..#".to_string(), "####".to_string()])
+        );
+        assert!(image.is_multiline());
+        assert_eq!(image.to_string(), "#..#\n####");
+        assert_eq!(Answer::parse(&image.to_string()), image);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(Answer::from("0123"), Answer::Text("0123".to_string()));
+        assert_eq!(Answer::parse("0123"), Answer::from("0123"));
+        assert_eq!(Answer::parse("+5"), Answer::Text("+5".to_string()));
+        assert_eq!(Answer::from(String::from("123")), Answer::Integer(123));
+        assert_eq!(
+            Answer::from(String::from("\nab\ncd\n")),
+            Answer::Image(vec!["ab".to_string(), "cd".to_string()])
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Checking solutions against the known answers for my inputs
+//!
+//! Answers live in `answers/YEAR.toml` at the root of the repository, or
+//! `answers/PROFILE/YEAR.toml` for a named [input profile](crate::input), one table per day:
+//!
+//! ```toml
+//! [day7]
+//! part1 = 1444896
+//! part2 = 404395
+//!
+//! [day10]
+//! part2 = '''
+//! ####.####
+//! #.......#
+//! '''
+//! ```
+//!
+//! Tests call [`assert_answer`] rather than hard-coding answers, so that a day with no input file
+//! or no recorded answer is skipped instead of failing, and a part which is still `todo!()` is
+//! reported as unsolved rather than panicking.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use crate::answer::Answer;
+use crate::input::{repo_root, InputError, Inputs};
+
+/// Problems reading an answers file
+#[derive(Debug)]
+pub enum AnswersError {
+    /// The file exists but couldn't be read
+    Read(PathBuf, io::Error),
+    /// The file isn't valid TOML
+    Toml(PathBuf, toml::de::Error),
+    /// Something in the file isn't a `dayN.partK` answer
+    Invalid(PathBuf, String),
+    /// The input to check against couldn't be read
+    Input(InputError),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, err) => write!(f, "can't read {}: {err}", path.display()),
+            Self::Toml(path, err) => write!(f, "can't parse {}: {err}", path.display()),
+            Self::Invalid(path, reason) => write!(f, "{}: {reason}", path.display()),
+            Self::Input(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl From<InputError> for AnswersError {
+    fn from(err: InputError) -> Self {
+        Self::Input(err)
+    }
+}
+
+/// The recorded answers for one year
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u32, u32), Answer>,
+}
+
+impl Answers {
+    /// Where the answers for `year` are kept, for a profile's inputs or my own
+    #[must_use]
+    pub fn path(year: u32, profile: Option<&str>) -> PathBuf {
+        let mut path = repo_root().join("answers");
+        if let Some(profile) = profile {
+            path.push(profile);
+        }
+        path.join(format!("{year}.toml"))
+    }
+
+    /// Load the answers for `year`, which are empty if there's no file for it yet
+    ///
+    /// # Errors
+    /// If the file exists but can't be read, or isn't in the expected format
+    pub fn load(year: u32, profile: Option<&str>) -> Result<Answers, AnswersError> {
+        let path = Self::path(year, profile);
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|err| match err {
+                AnswersError::Toml(_, e) => AnswersError::Toml(path, e),
+                AnswersError::Invalid(_, reason) => AnswersError::Invalid(path, reason),
+                other => other,
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(AnswersError::Read(path, err)),
+        }
+    }
+
+    /// Parse the contents of an answers file
+    ///
+    /// Answers can be integers or strings, and strings are interpreted like printed answers, so
+    /// multi-line strings are images.
+    ///
+    /// # Errors
+    /// If the text isn't valid TOML, or has anything other than `partN` keys in `[dayN]` tables
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let invalid = |reason: String| AnswersError::Invalid(PathBuf::new(), reason);
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| AnswersError::Toml(PathBuf::new(), e))?;
+
+        let mut answers = HashMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| invalid(format!("expected a dayN table, found {day_key:?}")))?;
+            let toml::Value::Table(parts) = parts else {
+                return Err(invalid(format!("{day_key} should be a table")));
+            };
+
+            for (part_key, value) in parts {
+                let part = part_key
+                    .strip_prefix("part")
+                    .and_then(|p| p.parse().ok())
+                    .ok_or_else(|| {
+                        invalid(format!("expected partN in {day_key}, found {part_key:?}"))
+                    })?;
+                let answer = match value {
+                    toml::Value::Integer(n) => Answer::Integer(n.into()),
+                    toml::Value::String(s) => Answer::parse(&s),
+                    other => {
+                        return Err(invalid(format!(
+                            "{day_key}.{part_key} should be an integer or string, found {}",
+                            other.type_str()
+                        )))
+                    }
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    /// The answer for a part, if it's been recorded
+    #[must_use]
+    pub fn get(&self, day: u32, part: u32) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    /// Compare a solution's answer with the recorded one
+    #[must_use]
+    pub fn compare(&self, day: u32, part: u32, actual: Answer) -> Outcome {
+        match self.get(day, part) {
+            None => Outcome::MissingAnswer,
+            Some(expected) if *expected == actual => Outcome::Correct,
+            Some(expected) => Outcome::Wrong {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+}
+
+/// The result of checking a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The solution gave the recorded answer
+    Correct,
+    /// The solution gave a different answer
+    Wrong {
+        /// The recorded answer
+        expected: Answer,
+        /// What the solution gave
+        actual: Answer,
+    },
+    /// The solution hasn't been written yet
+    Unsolved,
+    /// There's no input file for the day
+    MissingInput(PathBuf),
+    /// No answer has been recorded for the part
+    MissingAnswer,
+}
+
+impl Outcome {
+    /// Whether this shows a mistake, rather than work that hasn't been done yet
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Wrong { .. })
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong { expected, actual }
+                if expected.is_multiline() || actual.is_multiline() =>
+            {
+                write!(f, "wrong, expected:\n{expected}\nbut got:\n{actual}")
+            }
+            Outcome::Wrong { expected, actual } => {
+                write!(f, "wrong, expected {expected} but got {actual}")
+            }
+            Outcome::Unsolved => write!(f, "unsolved"),
+            Outcome::MissingInput(path) => write!(f, "no input at {}", path.display()),
+            Outcome::MissingAnswer => write!(f, "no answer recorded"),
+        }
+    }
+}
+
+/// Whether a panic message is the one left by `todo!()` or `unimplemented!()`
+#[must_use]
+pub fn is_unsolved_panic(message: &str) -> bool {
+    message.starts_with("not yet implemented") || message.starts_with("not implemented")
+}
+
+/// Run `solve` on the input for a day, and compare what it gives with the recorded answer
+///
+/// The input and answers are found as [`Inputs::from_env`] says. The solution is still run when
+/// there's no answer to compare with, so that unfinished parts are reported as unsolved.
+///
+/// # Errors
+/// If the answers file can't be loaded, or the input exists but can't be read
+///
+/// # Panics
+/// If `solve` panics for any reason other than being unfinished
+pub fn check<T, F>(year: u32, day: u32, part: u32, solve: F) -> Result<Outcome, AnswersError>
+where
+    T: Display,
+    F: FnOnce(&str) -> T,
+{
+    let inputs = Inputs::from_env()?;
+    let answers = Answers::load(year, inputs.profile())?;
+    let input = match inputs.load(year, day) {
+        Ok(input) => input,
+        Err(InputError::Missing { path, .. }) => return Ok(Outcome::MissingInput(path)),
+        Err(err) => return Err(err.into()),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(&input).to_string())) {
+        Ok(actual) => Ok(answers.compare(day, part, Answer::parse(&actual))),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned());
+            if message.as_deref().is_some_and(is_unsolved_panic) {
+                Ok(Outcome::Unsolved)
+            } else {
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Test that `solve` gives the recorded answer for my input, like [`check`]
+///
+/// Missing inputs and answers, and unsolved parts, are noted in the test's output but don't fail
+/// it.
+///
+/// # Panics
+/// If the answer is wrong, the answers file can't be loaded, or `solve` panics
+pub fn assert_answer<T, F>(year: u32, day: u32, part: u32, solve: F)
+where
+    T: Display,
+    F: FnOnce(&str) -> T,
+{
+    match check(year, day, part, solve) {
+        Ok(Outcome::Correct) => {}
+        Ok(outcome @ Outcome::Wrong { .. }) => {
+            panic!("{year} day {day} part {part}: {outcome}")
+        }
+        Ok(outcome) => println!("{year} day {day} part {part}: skipped, {outcome}"),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[day7]
+part1 = 1444896
+part2 = "3,6,3,7,0"
+
+[day10]
+part2 = '''
+#..#
+####
+'''
+"#;
+
+    #[test]
+    fn parsing() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(7, 1), Some(&Answer::Integer(1_444_896)));
+        assert_eq!(
+            answers.get(7, 2),
+            Some(&Answer::Text("3,6,3,7,0".to_string()))
+        );
+        assert_eq!(
+            answers.get(10, 2),
+            Some(&Answer::Image(vec!["#..#".to_string(), "####".to_string()]))
+        );
+        assert_eq!(answers.get(10, 1), None);
+
+        assert!(matches!(
+            Answers::parse("[day1]\npart1 = 1.5"),
+            Err(AnswersError::Invalid(..))
+        ));
+        assert!(matches!(
+            Answers::parse("[dayone]\npart1 = 1"),
+            Err(AnswersError::Invalid(..))
+        ));
+        assert!(matches!(
+            Answers::parse("[day1"),
+            Err(AnswersError::Toml(..))
+        ));
+    }
+
+    #[test]
+    fn outcomes() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.compare(7, 1, Answer::parse("1444896")),
+            Outcome::Correct
+        );
+        assert_eq!(
+            answers.compare(10, 2, Answer::parse("\n#..#\n####\n")),
+            Outcome::Correct
+        );
+        let wrong = answers.compare(7, 1, Answer::Integer(12));
+        assert!(wrong.is_failure());
+        assert_eq!(wrong.to_string(), "wrong, expected 1444896 but got 12");
+        assert_eq!(
+            answers.compare(8, 1, Answer::Integer(12)),
+            Outcome::MissingAnswer
+        );
+
+        assert!(is_unsolved_panic("not yet implemented"));
+        assert!(is_unsolved_panic("not implemented: part 2"));
+        assert!(!is_unsolved_panic("attempt to subtract with overflow"));
+    }
+
+    #[test]
+    fn answer_files_load() {
+        for year in 2015..=2030 {
+            Answers::load(year, None).unwrap();
+        }
+        let answers = Answers::load(2022, None).unwrap();
+        assert_eq!(answers.get(7, 1), Some(&Answer::Integer(1_444_896)));
+        assert!(answers.get(10, 2).is_some_and(Answer::is_multiline));
+    }
+}
@@ -1,8 +1,11 @@
-//! Simple arithmetic operations and reduction
+//! Simple arithmetic operations and reduction, and expression trees built from them
 
 use std::cmp::Ordering;
+use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Rem, Sub};
 
+use num_traits::Num;
+
 /// A simple arithmetic operation
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -116,3 +119,233 @@ where
         }
     }
 }
+
+/// An arithmetic expression tree over a single unknown
+///
+/// Use with an exact number type such as [`Rational`] so that division never loses information.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr<T> {
+    /// A known value
+    Const(T),
+    /// The unknown
+    Var,
+    /// An operation on two subexpressions, `left op right`
+    Op(Op, Box<Expr<T>>, Box<Expr<T>>),
+}
+
+/// Exact rational numbers, for use in [`Expr`]
+pub type Rational = num_rational::Ratio<i128>;
+
+/// Reasons [`Expr::solve`] can fail
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// Neither side of the equation contains the unknown
+    NoUnknown,
+    /// The unknown appears more than once, so it can't be isolated by inverting operations
+    RepeatedUnknown,
+    /// The equation has no solution, or no unique one (eg. because the unknown is multiplied by 0)
+    NotUnique,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoUnknown => write!(f, "equation doesn't contain the unknown"),
+            Self::RepeatedUnknown => write!(f, "unknown appears more than once"),
+            Self::NotUnique => write!(f, "equation has no unique solution"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl<T> Expr<T>
+where
+    T: Clone + Num,
+{
+    /// Build `left op right`, folding it straight away if both sides are known
+    #[must_use]
+    pub fn binary(op: Op, left: Self, right: Self) -> Self {
+        match (left, right) {
+            (Self::Const(l), Self::Const(r)) => Self::Const(op.apply(l, r)),
+            (l, r) => Self::Op(op, Box::new(l), Box::new(r)),
+        }
+    }
+
+    /// Evaluate every subexpression which doesn't depend on the unknown
+    #[must_use]
+    pub fn fold(self) -> Self {
+        match self {
+            Self::Op(op, l, r) => Self::binary(op, l.fold(), r.fold()),
+            other => other,
+        }
+    }
+
+    /// Get the value of the expression, if it doesn't depend on the unknown
+    #[must_use]
+    pub fn value(&self) -> Option<T> {
+        match self {
+            Self::Const(n) => Some(n.clone()),
+            Self::Var => None,
+            Self::Op(op, l, r) => Some(op.apply(l.value()?, r.value()?)),
+        }
+    }
+
+    /// Evaluate the expression with the unknown set to `var`
+    #[must_use]
+    pub fn eval(&self, var: &T) -> T {
+        match self {
+            Self::Const(n) => n.clone(),
+            Self::Var => var.clone(),
+            Self::Op(op, l, r) => op.apply(l.eval(var), r.eval(var)),
+        }
+    }
+
+    /// Count how many times the unknown appears
+    #[must_use]
+    pub fn var_count(&self) -> usize {
+        match self {
+            Self::Const(_) => 0,
+            Self::Var => 1,
+            Self::Op(_, l, r) => l.var_count() + r.var_count(),
+        }
+    }
+
+    /// Find the value of the unknown for which `self == other`
+    ///
+    /// Works by folding both sides, then peeling operations off the side with the unknown and
+    /// applying their inverses to the other side.
+    ///
+    /// # Errors
+    ///
+    /// If the unknown doesn't appear exactly once across both sides, or there isn't exactly one
+    /// value which satisfies the equation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use aoc_helpers::arith::{Expr, Op, Rational};
+    /// // 10 - (x / 4) == 7
+    /// let lhs = Expr::binary(
+    ///     Op::Sub,
+    ///     Expr::Const(Rational::from(10)),
+    ///     Expr::binary(Op::Div, Expr::Var, Expr::Const(Rational::from(4))),
+    /// );
+    /// assert_eq!(lhs.solve(Expr::Const(Rational::from(7))), Ok(Rational::from(12)));
+    /// ```
+    pub fn solve(self, other: Self) -> Result<T, SolveError> {
+        let (mut unknown, known) = match (self.fold(), other.fold()) {
+            (l, r) if l.var_count() + r.var_count() > 1 => return Err(SolveError::RepeatedUnknown),
+            (Self::Const(_), Self::Const(_)) => return Err(SolveError::NoUnknown),
+            (Self::Const(n), unknown) | (unknown, Self::Const(n)) => (unknown, n),
+            _ => unreachable!("folding leaves a side without the unknown as a constant"),
+        };
+        let mut target = known;
+
+        loop {
+            match unknown {
+                Self::Var => return Ok(target),
+                Self::Const(_) => unreachable!("the unknown is always on this side"),
+                Self::Op(op, l, r) => {
+                    let (next, known, var_on_left) = match (*l, *r) {
+                        (next, Self::Const(n)) => (next, n, true),
+                        (Self::Const(n), next) => (next, n, false),
+                        _ => {
+                            unreachable!("folding leaves a side without the unknown as a constant")
+                        }
+                    };
+                    target = Self::invert(op, target, known, var_on_left)?;
+                    unknown = next;
+                }
+            }
+        }
+    }
+
+    /// Solve `x op known == target` (or `known op x == target`, if `!var_on_left`) for `x`
+    fn invert(op: Op, target: T, known: T, var_on_left: bool) -> Result<T, SolveError> {
+        let zero = T::zero();
+        Ok(match (op, var_on_left) {
+            (Op::Add, _) => target - known,
+            (Op::Sub, true) => target + known,
+            (Op::Sub, false) => known - target,
+            // `x * 0` has no unique solution, and `x / 0` has none at all
+            (Op::Mul, _) | (Op::Div, true) if known == zero => return Err(SolveError::NotUnique),
+            (Op::Mul, _) => target / known,
+            (Op::Div, true) => target * known,
+            (Op::Div, false) if target == zero => return Err(SolveError::NotUnique),
+            (Op::Div, false) => known / target,
+        })
+    }
+}
+
+impl<T: Display> Display for Expr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Const(n) => write!(f, "{n}"),
+            Self::Var => write!(f, "x"),
+            Self::Op(op, l, r) => write!(f, "({l} {op} {r})"),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(x: i128) -> Expr<Rational> {
+        Expr::Const(Rational::from(x))
+    }
+
+    #[test]
+    fn folding() {
+        let e = Expr::Op(
+            Op::Add,
+            Box::new(Expr::Op(Op::Div, Box::new(n(7)), Box::new(n(2)))),
+            Box::new(Expr::Op(Op::Mul, Box::new(Expr::Var), Box::new(n(3)))),
+        );
+        assert_eq!(e.to_string(), "((7 / 2) + (x * 3))");
+        assert_eq!(e.value(), None);
+        assert_eq!(e.eval(&Rational::from(1)), Rational::new(13, 2));
+        assert_eq!(e.fold().to_string(), "(7/2 + (x * 3))");
+        assert_eq!(Expr::binary(Op::Sub, n(1), n(3)), n(-2));
+    }
+
+    #[test]
+    fn operand_order() {
+        let sub = |l, r| Expr::binary(Op::Sub, l, r);
+        let div = |l, r| Expr::binary(Op::Div, l, r);
+        // x - 3 == 5, 3 - x == 5
+        assert_eq!(sub(Expr::Var, n(3)).solve(n(5)), Ok(Rational::from(8)));
+        assert_eq!(sub(n(3), Expr::Var).solve(n(5)), Ok(Rational::from(-2)));
+        // x / 3 == 5, 3 / x == 5
+        assert_eq!(div(Expr::Var, n(3)).solve(n(5)), Ok(Rational::from(15)));
+        assert_eq!(div(n(3), Expr::Var).solve(n(5)), Ok(Rational::new(3, 5)));
+        // Works from either side
+        assert_eq!(n(5).solve(sub(n(3), Expr::Var)), Ok(Rational::from(-2)));
+    }
+
+    #[test]
+    fn unsolvable() {
+        let mul = |l, r| Expr::binary(Op::Mul, l, r);
+        let div = |l, r| Expr::binary(Op::Div, l, r);
+        assert_eq!(n(1).solve(n(1)), Err(SolveError::NoUnknown));
+        assert_eq!(
+            mul(Expr::Var, Expr::Var).solve(n(4)),
+            Err(SolveError::RepeatedUnknown)
+        );
+        assert_eq!(mul(Expr::Var, n(0)).solve(n(4)), Err(SolveError::NotUnique));
+        assert_eq!(div(Expr::Var, n(0)).solve(n(4)), Err(SolveError::NotUnique));
+    }
+}
@@ -0,0 +1,174 @@
+//! Cycle detection, for extrapolating simulations out to huge step counts
+//!
+//! Two flavours are provided: hash-based detection, which remembers every state it has seen and
+//! so never repeats work, and [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm),
+//! which only needs states to be comparable and uses constant memory at the cost of re-running
+//! the simulation.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of the sequence produced by repeatedly stepping a state
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the sequence first enters the cycle
+    pub start: usize,
+    /// Number of steps taken to go around the cycle once
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Find the earliest step which gives the same state as step `n`
+    #[must_use]
+    pub fn reduce(self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `initial`, by hashing every state
+///
+/// Also returns every state seen, in order, which covers at least one full trip around the cycle.
+pub fn find_hashed<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut current = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&current) {
+            let len = history.len() - start;
+            return (Cycle { start, len }, history);
+        }
+        seen.insert(current.clone(), history.len());
+        let next = step(&current);
+        history.push(current);
+        current = next;
+    }
+}
+
+/// Find the cycle reached by repeatedly applying `step` to `initial`, with Brent's algorithm
+pub fn find_brent<S, F>(initial: &S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the cycle length by having the hare race ahead in ever-growing powers of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // Then find the start by walking two pointers `len` apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+/// Get the state after applying `step` to `initial` `n` times, using hash-based detection
+///
+/// Stops early if `n` is reached before any repetition.
+pub fn state_after<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut current = initial;
+
+    while history.len() < n {
+        if let Some(&start) = seen.get(&current) {
+            let cycle = Cycle {
+                start,
+                len: history.len() - start,
+            };
+            return history.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(current.clone(), history.len());
+        let next = step(&current);
+        history.push(current);
+        current = next;
+    }
+
+    current
+}
+
+/// Get the state after applying `step` to `initial` `n` times, using Brent's algorithm
+pub fn state_after_brent<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let cycle = find_brent(&initial, &mut step);
+    (0..cycle.reduce(n)).fold(initial, |s, _| step(&s))
+}
+
+/// Evaluate `value` on the state after applying `step` to `initial` `n` times
+///
+/// Uses hash-based detection.
+pub fn value_after<S, V, F, G>(initial: S, step: F, n: usize, value: G) -> V
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+    G: FnOnce(&S) -> V,
+{
+    value(&state_after(initial, step, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `x -> x^2 + 1 (mod 255)` starting from 3 has a tail of 2 and a cycle of 6
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn step(&x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn detection_agrees() {
+        let (hashed, history) = find_hashed(3, step);
+        assert_eq!(hashed, find_brent(&3, step));
+        assert_eq!(hashed, Cycle { start: 2, len: 6 });
+        assert_eq!(history.len(), hashed.start + hashed.len);
+    }
+
+    #[test]
+    fn extrapolate() {
+        let direct = |n| (0..n).fold(3, |s, _| step(&s));
+        for n in [0, 1, 2, 7, 8, 100, 1001] {
+            assert_eq!(state_after(3, step, n), direct(n), "n = {n}");
+            assert_eq!(state_after_brent(3, step, n), direct(n), "n = {n}");
+        }
+        assert_eq!(
+            value_after(3, step, 1_000_000_000, |&s| s * 2),
+            direct(2 + (1_000_000_000 - 2) % 6) * 2
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::{grid_impls, Grid};
+use crate::{IPoint, UPoint};
+
+/// A fixed-size grid stored as a flat, row-major `Vec`
+///
+/// Indexed by [`UPoint`], with `(0, 0)` at the top-left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dense<T> {
+    width: usize,
+    height: usize,
+    elems: Vec<T>,
+}
+
+impl<T: Clone> Dense<T> {
+    /// Create a new grid of the given size, filled with `elem`
+    #[must_use]
+    pub fn new(width: usize, height: usize, elem: T) -> Self {
+        Self {
+            width,
+            height,
+            elems: vec![elem; width * height],
+        }
+    }
+}
+
+impl<T> Dense<T> {
+    /// Create a new grid from a flat, row-major list of elements
+    ///
+    /// # Panics
+    ///
+    /// If the number of elements is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, elems: Vec<T>) -> Self {
+        assert!(
+            width != 0 && elems.len().is_multiple_of(width),
+            "{} elements cannot be split into rows of {width}",
+            elems.len()
+        );
+        Self {
+            width,
+            height: elems.len() / width,
+            elems,
+        }
+    }
+
+    /// Create a new grid from an iterator of rows
+    ///
+    /// # Panics
+    ///
+    /// If the rows are not all the same length.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut width = None;
+        let mut elems = Vec::new();
+
+        for (y, row) in rows.into_iter().enumerate() {
+            let before = elems.len();
+            elems.extend(row);
+            let len = elems.len() - before;
+            let expected = *width.get_or_insert(len);
+            assert_eq!(
+                len, expected,
+                "row {y} has a different length to the rows above"
+            );
+        }
+
+        let width = width.unwrap_or(0);
+        Self {
+            width,
+            height: elems.len().checked_div(width).unwrap_or(0),
+            elems,
+        }
+    }
+
+    /// The elements of the grid, in row-major order
+    #[must_use]
+    pub fn elems(&self) -> &[T] {
+        &self.elems
+    }
+
+    /// Mutable access to the elements of the grid, in row-major order
+    #[must_use]
+    pub fn elems_mut(&mut self) -> &mut [T] {
+        &mut self.elems
+    }
+
+    /// Create a new grid of the same shape by applying `f` to every element
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Dense<U> {
+        Dense {
+            width: self.width,
+            height: self.height,
+            elems: self.elems.iter().map(f).collect(),
+        }
+    }
+
+    #[must_use]
+    fn flat_index(&self, (x, y): UPoint) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T> Grid for Dense<T> {
+    type Point = UPoint;
+    type Elem = T;
+
+    fn dims(&self) -> UPoint {
+        (self.width, self.height)
+    }
+
+    fn point_at(&self, pos: UPoint) -> UPoint {
+        pos
+    }
+
+    fn get(&self, point: UPoint) -> Option<&T> {
+        self.elems.get(self.flat_index(point)?)
+    }
+
+    fn get_mut(&mut self, point: UPoint) -> Option<&mut T> {
+        let i = self.flat_index(point)?;
+        self.elems.get_mut(i)
+    }
+
+    fn offset(&self, (x, y): UPoint, (dx, dy): IPoint) -> Option<UPoint> {
+        let moved = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.flat_index(moved).map(|_| moved)
+    }
+}
+
+grid_impls!(Dense, UPoint);
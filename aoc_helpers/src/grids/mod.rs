@@ -0,0 +1,324 @@
+//! Two-dimensional grids, with a common interface over several storage backends
+//!
+//! All grids are displayed and iterated in row-major order, with `y` increasing downwards.
+//! Every backend implements [`Grid`], so algorithms written against the trait work regardless of
+//! how the elements are stored:
+//!
+//! - [`Dense`]: a flat, row-major `Vec`, indexed by [`UPoint`]
+//! - [`Offset`]: a dense grid whose top-left corner may sit anywhere, indexed by [`IPoint`]
+//! - [`Toroidal`]: a dense grid which wraps around at its edges, indexed by [`IPoint`]
+//! - [`Sparse`]: a hashmap of points, with a default for everything else, indexed by [`IPoint`]
+//!
+//! Character maps, as found in most puzzle inputs, can be read into a [`Dense`] grid with
+//! [`parse`], and split into connected [`regions`].
+
+use std::fmt::{self, Display};
+
+use super::{IPoint, UPoint};
+
+mod dense;
+mod offset;
+mod parse;
+mod regions;
+mod sparse;
+mod toroidal;
+
+pub use dense::Dense;
+pub use offset::Offset;
+pub use parse::{parse, ParseError, Parsed};
+pub use regions::{regions, Region, Regions};
+pub use sparse::Sparse;
+pub use toroidal::{toroidal_index_single, Toroidal};
+
+/// Adjacency method for a 2D grid
+pub trait Adjacency {
+    /// Relative offsets of adjacent points to a given location in the grid
+    const OFFSETS: &'static [IPoint];
+
+    /// Check if two points are adjacent in this system
+    #[must_use]
+    fn adjacent((lx, ly): IPoint, (rx, ry): IPoint) -> bool {
+        Self::OFFSETS.contains(&(rx - lx, ry - ly))
+    }
+}
+
+/// Orthogonal adjacency system marker
+#[derive(Copy, Clone, Debug)]
+pub struct Orthogonal;
+impl Adjacency for Orthogonal {
+    /// Orthogonal direction offsets
+    ///
+    /// Runs clockwise from "directly up".
+    const OFFSETS: &'static [IPoint] = &[(0, -1), (1, 0), (0, 1), (-1, 0)];
+}
+
+/// Diagonal **and orthogonal** adjacency marker
+#[derive(Copy, Clone, Debug)]
+pub struct Diagonal;
+impl Adjacency for Diagonal {
+    /// Orthogonal and diagonal direction offsets
+    ///
+    /// Runs clockwise from "directly up".
+    const OFFSETS: &'static [IPoint] = &[
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+}
+
+/// Common interface for two-dimensional grids
+///
+/// Implementors need only describe their shape, how to look elements up and how to move between
+/// points; everything else is built on top of that.
+pub trait Grid {
+    /// Coordinate type used to index the grid
+    type Point: Copy + Eq + fmt::Debug;
+    /// Type of the elements stored in the grid
+    type Elem;
+
+    /// The `(width, height)` of the grid
+    fn dims(&self) -> UPoint;
+
+    /// Convert a 0-based `(column, row)` position, counted from the top-left, to a point in the grid
+    fn point_at(&self, pos: UPoint) -> Self::Point;
+
+    /// Get a reference to the element at `point`, or `None` if it lies outside the grid
+    fn get(&self, point: Self::Point) -> Option<&Self::Elem>;
+
+    /// Get a mutable reference to the element at `point`, or `None` if it lies outside the grid
+    fn get_mut(&mut self, point: Self::Point) -> Option<&mut Self::Elem>;
+
+    /// Move `point` by the vector `by`
+    ///
+    /// Returns `None` if the resulting point would lie outside the grid.
+    fn offset(&self, point: Self::Point, by: IPoint) -> Option<Self::Point>;
+
+    /// Width of the grid
+    fn width(&self) -> usize {
+        self.dims().0
+    }
+
+    /// Height of the grid
+    fn height(&self) -> usize {
+        self.dims().1
+    }
+
+    /// Check if a point falls within the grid
+    fn contains(&self, point: Self::Point) -> bool {
+        self.get(point).is_some()
+    }
+
+    /// Iterate over every point in the grid, in row-major order
+    fn points(&self) -> impl Iterator<Item = Self::Point> {
+        let (width, height) = self.dims();
+        (0..height).flat_map(move |y| (0..width).map(move |x| self.point_at((x, y))))
+    }
+
+    /// Iterate over all points in the grid and their elements, in row-major order
+    fn iter_all(&self) -> impl Iterator<Item = (Self::Point, &Self::Elem)> {
+        self.points().filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Iterate over the `y`th row of the grid, counted from the top
+    fn row(&self, y: usize) -> impl Iterator<Item = (Self::Point, &Self::Elem)> {
+        (0..self.width()).filter_map(move |x| {
+            let p = self.point_at((x, y));
+            Some((p, self.get(p)?))
+        })
+    }
+
+    /// Iterate over the `x`th column of the grid, counted from the left
+    fn column(&self, x: usize) -> impl Iterator<Item = (Self::Point, &Self::Elem)> {
+        (0..self.height()).filter_map(move |y| {
+            let p = self.point_at((x, y));
+            Some((p, self.get(p)?))
+        })
+    }
+
+    /// Iterate over points adjacent to `point`, under the adjacency system `A`
+    fn adj_coords<A: Adjacency>(&self, point: Self::Point) -> impl Iterator<Item = Self::Point> {
+        A::OFFSETS
+            .iter()
+            .filter_map(move |&by| self.offset(point, by))
+    }
+
+    /// Iterate over elements adjacent to `point`, under the adjacency system `A`
+    ///
+    /// Returns an iterator over coordinate-element tuples.
+    fn neighbours<A: Adjacency>(
+        &self,
+        point: Self::Point,
+    ) -> impl Iterator<Item = (Self::Point, &Self::Elem)> {
+        self.adj_coords::<A>(point)
+            .filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Iterate over the points met by stepping repeatedly from `start` by `step`
+    ///
+    /// `start` itself is not included.
+    /// On grids which wrap around (ie. [`Toroidal`]) this will never end.
+    fn raycast(&self, start: Self::Point, step: IPoint) -> Raycast<'_, Self>
+    where
+        Self: Sized,
+    {
+        Raycast {
+            grid: self,
+            cursor: Some(start),
+            step,
+        }
+    }
+
+    /// Get a [`Display`]able view of the grid
+    fn display(&self) -> GridDisplay<'_, Self>
+    where
+        Self: Sized,
+        Self::Elem: Display,
+    {
+        GridDisplay(self)
+    }
+}
+
+/// Iterator over coordinates in a particular raycast direction
+#[derive(Clone, Debug)]
+pub struct Raycast<'a, G: Grid> {
+    grid: &'a G,
+    cursor: Option<G::Point>,
+    step: IPoint,
+}
+
+impl<G: Grid> Iterator for Raycast<'_, G> {
+    type Item = G::Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor = self.grid.offset(self.cursor?, self.step);
+        self.cursor
+    }
+}
+
+impl<G: Grid> std::iter::FusedIterator for Raycast<'_, G> {}
+
+/// Displays a grid row-by-row, see [`Grid::display`]
+#[derive(Debug)]
+pub struct GridDisplay<'a, G>(&'a G);
+
+impl<G> Display for GridDisplay<'_, G>
+where
+    G: Grid,
+    G::Elem: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.0.height() {
+            for (_, elem) in self.0.row(y) {
+                write!(f, "{elem}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Implement [`std::ops::Index`], [`std::ops::IndexMut`] and [`Display`] for a [`Grid`] backend
+macro_rules! grid_impls {
+    ($backend:ident, $point:ty $(, $bound:path)?) => {
+        impl<T $(: $bound)?> std::ops::Index<$point> for $backend<T> {
+            type Output = T;
+
+            fn index(&self, index: $point) -> &Self::Output {
+                self.get(index)
+                    .unwrap_or_else(|| panic!("{index:?} is outside of the grid"))
+            }
+        }
+
+        impl<T $(: $bound)?> std::ops::IndexMut<$point> for $backend<T> {
+            fn index_mut(&mut self, index: $point) -> &mut Self::Output {
+                self.get_mut(index)
+                    .unwrap_or_else(|| panic!("{index:?} is outside of the grid"))
+            }
+        }
+
+        impl<T: std::fmt::Display $(+ $bound)?> std::fmt::Display for $backend<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                $crate::grids::Grid::display(self).fmt(f)
+            }
+        }
+    };
+}
+use grid_impls;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Dense<u8> {
+        Dense::from_rows(["abc", "def"].map(|l| l.bytes()))
+    }
+
+    #[test]
+    fn dense_iteration() {
+        let grid = sample();
+        assert_eq!(grid.dims(), (3, 2));
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(
+            grid.row(1).map(|(_, &c)| c).collect::<Vec<_>>(),
+            b"def".to_vec()
+        );
+        assert_eq!(
+            grid.column(0).map(|(_, &c)| c).collect::<Vec<_>>(),
+            b"ad".to_vec()
+        );
+        assert_eq!(grid.iter_all().count(), 6);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = sample();
+        assert_eq!(
+            grid.adj_coords::<Orthogonal>((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours::<Diagonal>((1, 0)).count(), 5);
+        assert!(Diagonal::adjacent((0, 0), (-1, 1)));
+        assert!(!Orthogonal::adjacent((0, 0), (-1, 1)));
+    }
+
+    #[test]
+    fn raycasts() {
+        let grid = Dense::new(5, 5, ());
+        assert_eq!(
+            grid.raycast((0, 0), (2, 1)).collect::<Vec<_>>(),
+            vec![(2, 1), (4, 2)]
+        );
+
+        let torus = Toroidal::new(grid);
+        assert_eq!(
+            torus.raycast((0, 0), (2, 1)).nth(2),
+            Some((1, 3)),
+            "raycasts should wrap on a torus"
+        );
+    }
+
+    #[test]
+    fn offset_expands() {
+        let mut grid = Offset::new((-2, -1), (0, 0), '.');
+        grid[(-2, -1)] = '#';
+        grid.expand(1, 0, 0, 2, '.');
+        assert_eq!(grid.limits(), ((-2, -2), (2, 0)));
+        assert_eq!(grid.to_string(), ".....\n#....\n.....\n");
+        assert!(!grid.contains((3, 0)));
+    }
+
+    #[test]
+    fn sparse_bounds() {
+        let mut grid = Sparse::new('.');
+        grid.insert((1, -1), '#');
+        grid.insert((-1, 1), '#');
+        assert_eq!(grid.dims(), (3, 3));
+        assert_eq!(grid.to_string(), "..#\n...\n#..\n");
+        assert_eq!(grid.get((5, 5)), None);
+    }
+}
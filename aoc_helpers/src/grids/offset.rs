@@ -0,0 +1,101 @@
+use super::{grid_impls, Dense, Grid};
+use crate::{IPoint, UPoint};
+
+/// A dense grid which has inbuilt indexing logic to work with non-0-based (and negative) indexing
+///
+/// Indexed by [`IPoint`], with `y` increasing downwards.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Offset<T> {
+    /// Top-left allowed coordinate
+    origin: IPoint,
+    inner: Dense<T>,
+}
+
+impl<T: Clone> Offset<T> {
+    /// Create a new offset grid
+    ///
+    /// The first argument is the top-left allowed coordinate (inclusive), the second is the
+    /// bottom-right (inclusive).
+    /// `elem` is the element to fill the grid with.
+    ///
+    /// # Panics
+    ///
+    /// If `max` lies above or to the left of `min`.
+    #[must_use]
+    pub fn new(min @ (x0, y0): IPoint, (x1, y1): IPoint, elem: T) -> Self {
+        assert!(x1 >= x0 && y1 >= y0, "limits are the wrong way round");
+        Self {
+            origin: min,
+            inner: Dense::new((x1 - x0 + 1) as usize, (y1 - y0 + 1) as usize, elem),
+        }
+    }
+
+    /// Expand the grid in some combination of directions
+    ///
+    /// Pads with `elem`.
+    pub fn expand(&mut self, top: usize, bottom: usize, left: usize, right: usize, elem: T) {
+        let (ox, oy) = self.inner.dims();
+        let mut new = Dense::new(ox + left + right, oy + top + bottom, elem);
+
+        for ((x, y), e) in self.inner.iter_all() {
+            new[(x + left, y + top)] = e.clone();
+        }
+
+        self.inner = new;
+        self.origin = (self.origin.0 - left as isize, self.origin.1 - top as isize);
+    }
+}
+
+impl<T> Offset<T> {
+    /// (Top-left, bottom-right) coordinates of the grid, both inclusive
+    #[must_use]
+    pub fn limits(&self) -> (IPoint, IPoint) {
+        let (w, h) = self.inner.dims();
+        let (x0, y0) = self.origin;
+        (self.origin, (x0 + w as isize - 1, y0 + h as isize - 1))
+    }
+
+    /// The underlying 0-based grid
+    #[must_use]
+    pub fn inner(&self) -> &Dense<T> {
+        &self.inner
+    }
+
+    #[must_use]
+    fn convert_index(&self, (x, y): IPoint) -> Option<UPoint> {
+        let (left, top) = self.origin;
+        Some((
+            usize::try_from(x - left).ok()?,
+            usize::try_from(y - top).ok()?,
+        ))
+    }
+}
+
+impl<T> Grid for Offset<T> {
+    type Point = IPoint;
+    type Elem = T;
+
+    fn dims(&self) -> UPoint {
+        self.inner.dims()
+    }
+
+    fn point_at(&self, (x, y): UPoint) -> IPoint {
+        (self.origin.0 + x as isize, self.origin.1 + y as isize)
+    }
+
+    fn get(&self, point: IPoint) -> Option<&T> {
+        self.inner.get(self.convert_index(point)?)
+    }
+
+    fn get_mut(&mut self, point: IPoint) -> Option<&mut T> {
+        let index = self.convert_index(point)?;
+        self.inner.get_mut(index)
+    }
+
+    fn offset(&self, (x, y): IPoint, (dx, dy): IPoint) -> Option<IPoint> {
+        let moved = (x + dx, y + dy);
+        self.contains(moved).then_some(moved)
+    }
+}
+
+grid_impls!(Offset, IPoint);
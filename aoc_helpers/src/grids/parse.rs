@@ -0,0 +1,198 @@
+//! Parsing character maps into grids
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use super::Dense;
+use crate::UPoint;
+
+/// A grid parsed by [`parse`], along with the locations of any marker characters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parsed<T> {
+    /// The parsed grid
+    pub grid: Dense<T>,
+    /// Locations of each marker character, in row-major order
+    pub markers: HashMap<u8, Vec<UPoint>>,
+}
+
+impl<T> Parsed<T> {
+    /// Get the locations at which `marker` appeared
+    ///
+    /// Returns an empty slice if it didn't appear at all.
+    #[must_use]
+    pub fn all(&self, marker: u8) -> &[UPoint] {
+        self.markers.get(&marker).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the location of a marker which should appear exactly once, such as a start or end point
+    ///
+    /// Returns `None` if `marker` didn't appear or appeared more than once.
+    #[must_use]
+    pub fn single(&self, marker: u8) -> Option<UPoint> {
+        match self.all(marker) {
+            &[point] => Some(point),
+            _ => None,
+        }
+    }
+}
+
+/// Problems encountered by [`parse`]
+///
+/// Line and column numbers are 1-based.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// There were no lines to parse
+    Empty,
+    /// A line was a different length to the first
+    Ragged {
+        /// Line number
+        line: usize,
+        /// Length of the first line
+        expected: usize,
+        /// Length of this line
+        found: usize,
+    },
+    /// The mapping function didn't recognise a character
+    UnknownChar {
+        /// Line number
+        line: usize,
+        /// Column number
+        column: usize,
+        /// The offending character
+        ch: u8,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no lines in grid"),
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has length {found}, but previous lines have length {expected}"
+            ),
+            Self::UnknownChar { line, column, ch } => write!(
+                f,
+                "unknown character {:?} at line {line}, column {column}",
+                char::from(*ch)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a character map into a [`Dense`] grid
+///
+/// Every byte is converted to an element by `mapping`, which returns `None` for bytes it doesn't
+/// recognise.
+/// The locations of any bytes in `markers` are collected as well; they are still passed to
+/// `mapping`, which decides what lies underneath them.
+///
+/// # Errors
+///
+/// If the input is empty, isn't rectangular or `mapping` rejects a character.
+///
+/// # Example
+///
+/// ```
+/// # use aoc_helpers::grids::{parse, Grid};
+/// let parsed = parse("#S.\n.#E", b"SE", |b| match b {
+///     b'#' => Some(false),
+///     b'.' | b'S' | b'E' => Some(true),
+///     _ => None,
+/// })
+/// .unwrap();
+/// assert_eq!(parsed.single(b'S'), Some((1, 0)));
+/// assert_eq!(parsed.single(b'E'), Some((2, 1)));
+/// assert_eq!(parsed.grid[(1, 1)], false);
+/// ```
+pub fn parse<T>(
+    input: &str,
+    markers: &[u8],
+    mut mapping: impl FnMut(u8) -> Option<T>,
+) -> Result<Parsed<T>, ParseError> {
+    let mut width = None;
+    let mut elems = Vec::with_capacity(input.len());
+    let mut found = HashMap::new();
+
+    for (y, line) in input.lines().enumerate() {
+        let expected = *width.get_or_insert(line.len());
+        if line.len() != expected {
+            return Err(ParseError::Ragged {
+                line: y + 1,
+                expected,
+                found: line.len(),
+            });
+        }
+
+        for (x, ch) in line.bytes().enumerate() {
+            if markers.contains(&ch) {
+                found.entry(ch).or_insert_with(Vec::new).push((x, y));
+            }
+            elems.push(mapping(ch).ok_or(ParseError::UnknownChar {
+                line: y + 1,
+                column: x + 1,
+                ch,
+            })?);
+        }
+    }
+
+    match width {
+        None | Some(0) => Err(ParseError::Empty),
+        Some(width) => Ok(Parsed {
+            grid: Dense::from_vec(width, elems),
+            markers: found,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(b: u8) -> Option<u8> {
+        b.is_ascii_digit().then(|| b - b'0')
+    }
+
+    fn digits_or_x(b: u8) -> Option<u8> {
+        if b == b'x' {
+            Some(0)
+        } else {
+            digits(b)
+        }
+    }
+
+    #[test]
+    fn markers() {
+        let parsed = parse("1x2\r\n3x4\r\n", b"x", digits_or_x).unwrap();
+        assert_eq!(parsed.all(b'x'), &[(1, 0), (1, 1)]);
+        assert_eq!(parsed.single(b'x'), None);
+        assert_eq!(parsed.all(b'y'), &[]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("", b"", digits), Err(ParseError::Empty));
+        assert_eq!(
+            parse("12\n3", b"", digits),
+            Err(ParseError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            parse("12\n3?", b"", digits),
+            Err(ParseError::UnknownChar {
+                line: 2,
+                column: 2,
+                ch: b'?'
+            })
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Connected-component labelling
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{Adjacency, Grid, Orthogonal};
+
+/// A connected region of a grid, found by [`regions`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region<P> {
+    /// Every point in the region, starting with its first in row-major order
+    pub points: Vec<P>,
+    /// Number of cell edges separating the region from the rest of the grid
+    pub perimeter: usize,
+    /// Number of straight sides to the region's outline, including around any holes
+    pub sides: usize,
+}
+
+impl<P> Region<P> {
+    /// Number of points in the region
+    #[must_use]
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+}
+
+/// Every region in a grid, along with which region each point belongs to
+#[derive(Clone, Debug)]
+pub struct Regions<P> {
+    /// The regions, ordered by their first point in row-major order
+    pub regions: Vec<Region<P>>,
+    labels: HashMap<P, usize>,
+}
+
+impl<P: Eq + Hash> Regions<P> {
+    /// Get the index into [`Regions::regions`] of the region containing `point`
+    #[must_use]
+    pub fn label(&self, point: &P) -> Option<usize> {
+        self.labels.get(point).copied()
+    }
+
+    /// Get the region containing `point`
+    #[must_use]
+    pub fn region_of(&self, point: &P) -> Option<&Region<P>> {
+        self.regions.get(self.label(point)?)
+    }
+}
+
+/// Split `grid` into regions of points connected under the adjacency system `A`
+///
+/// Two adjacent points are in the same region if `connected` holds for their elements.
+/// Every point ends up in exactly one region, so filter the result if some elements (eg. walls)
+/// aren't interesting.
+///
+/// Perimeters and sides are always measured along the orthogonal edges of the cells, whichever
+/// adjacency system is used to connect them.
+pub fn regions<A, G>(
+    grid: &G,
+    mut connected: impl FnMut(&G::Elem, &G::Elem) -> bool,
+) -> Regions<G::Point>
+where
+    A: Adjacency,
+    G: Grid,
+    G::Point: Hash,
+{
+    let mut labels = HashMap::new();
+    let mut members = Vec::new();
+
+    for (start, _) in grid.iter_all() {
+        if labels.contains_key(&start) {
+            continue;
+        }
+
+        let label = members.len();
+        labels.insert(start, label);
+        let mut points = Vec::new();
+        let mut stack = vec![start];
+
+        // Flood fill out from the start
+        while let Some(point) = stack.pop() {
+            points.push(point);
+            let Some(here) = grid.get(point) else {
+                continue;
+            };
+            for (next, there) in grid.neighbours::<A>(point) {
+                if !labels.contains_key(&next) && connected(here, there) {
+                    labels.insert(next, label);
+                    stack.push(next);
+                }
+            }
+        }
+
+        members.push(points);
+    }
+
+    let regions = members
+        .into_iter()
+        .enumerate()
+        .map(|(label, points)| {
+            let member = |point: &G::Point| labels.get(point) == Some(&label);
+            let inside = |point: G::Point, by| grid.offset(point, by).is_some_and(|p| member(&p));
+
+            let mut perimeter = 0;
+            let mut sides = 0;
+            for &point in &points {
+                for &dir @ (dx, dy) in Orthogonal::OFFSETS {
+                    if inside(point, dir) {
+                        continue;
+                    }
+                    perimeter += 1;
+
+                    // Only count each side once, at the end where the neighbour along the side
+                    // (rotated a quarter turn from the edge) doesn't continue it
+                    let along = (-dy, dx);
+                    let continues = grid
+                        .offset(point, along)
+                        .is_some_and(|next| member(&next) && !inside(next, dir));
+                    if !continues {
+                        sides += 1;
+                    }
+                }
+            }
+
+            Region {
+                points,
+                perimeter,
+                sides,
+            }
+        })
+        .collect();
+
+    Regions { regions, labels }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grids::{Dense, Diagonal};
+
+    fn garden(input: &str) -> Dense<u8> {
+        Dense::from_rows(input.lines().map(str::bytes))
+    }
+
+    #[test]
+    fn metrics() {
+        let found = regions::<Orthogonal, _>(&garden("AAAA\nBBCD\nBBCC\nEEEC"), u8::eq);
+        let summary: Vec<_> = found
+            .regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter, r.sides))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(found.label(&(3, 2)), Some(2));
+    }
+
+    #[test]
+    fn holes() {
+        let found = regions::<Orthogonal, _>(&garden("OOOOO\nOXOXO\nOOOOO"), u8::eq);
+        let outer = found.region_of(&(0, 0)).unwrap();
+        assert_eq!((outer.area(), outer.perimeter, outer.sides), (13, 24, 12));
+        assert_eq!(found.regions.len(), 3);
+    }
+
+    #[test]
+    fn diagonal() {
+        let grid = garden("X.\n.X");
+        assert_eq!(regions::<Orthogonal, _>(&grid, u8::eq).regions.len(), 4);
+        let found = regions::<Diagonal, _>(&grid, u8::eq);
+        assert_eq!(found.regions.len(), 2);
+        assert_eq!(found.regions[0].perimeter, 8);
+    }
+}
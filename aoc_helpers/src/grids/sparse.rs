@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use super::{grid_impls, Grid};
+use crate::{IPoint, UPoint};
+
+/// A grid which only stores the points that have been set, with a default for everything else
+///
+/// Indexed by [`IPoint`].
+/// The grid covers the bounding box of every point inserted so far; reading a point inside it
+/// which hasn't been set gives the default element, while writing to one inserts it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sparse<T> {
+    elems: HashMap<IPoint, T>,
+    default: T,
+    /// (Top-left, bottom-right), both inclusive
+    bounds: Option<(IPoint, IPoint)>,
+}
+
+impl<T> Sparse<T> {
+    /// Create a new, empty grid, where unset points hold `default`
+    #[must_use]
+    pub fn new(default: T) -> Self {
+        Self {
+            elems: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// Set the element at `point`, growing the grid if necessary
+    ///
+    /// Returns the element previously set at `point`, if there was one.
+    pub fn insert(&mut self, point @ (x, y): IPoint, elem: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some(((x0, y0), (x1, y1))) => ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))),
+        });
+        self.elems.insert(point, elem)
+    }
+
+    /// Unset the element at `point`, returning it if it was set
+    ///
+    /// The grid does not shrink.
+    pub fn remove(&mut self, point: IPoint) -> Option<T> {
+        self.elems.remove(&point)
+    }
+
+    /// (Top-left, bottom-right) coordinates of the grid, both inclusive
+    ///
+    /// Returns `None` if nothing has been inserted yet.
+    #[must_use]
+    pub fn limits(&self) -> Option<(IPoint, IPoint)> {
+        self.bounds
+    }
+
+    /// Iterate over only the points which have been set
+    pub fn iter_set(&self) -> impl Iterator<Item = (IPoint, &T)> {
+        self.elems.iter().map(|(&p, e)| (p, e))
+    }
+
+    /// The number of points which have been set
+    #[must_use]
+    pub fn set_count(&self) -> usize {
+        self.elems.len()
+    }
+
+    #[must_use]
+    fn in_bounds(&self, (x, y): IPoint) -> bool {
+        self.bounds
+            .is_some_and(|((x0, y0), (x1, y1))| (x0..=x1).contains(&x) && (y0..=y1).contains(&y))
+    }
+}
+
+impl<T: Clone> Grid for Sparse<T> {
+    type Point = IPoint;
+    type Elem = T;
+
+    fn dims(&self) -> UPoint {
+        self.bounds.map_or((0, 0), |((x0, y0), (x1, y1))| {
+            ((x1 - x0 + 1) as usize, (y1 - y0 + 1) as usize)
+        })
+    }
+
+    fn point_at(&self, (x, y): UPoint) -> IPoint {
+        let (x0, y0) = self.bounds.map_or((0, 0), |b| b.0);
+        (x0 + x as isize, y0 + y as isize)
+    }
+
+    fn get(&self, point: IPoint) -> Option<&T> {
+        self.in_bounds(point)
+            .then(|| self.elems.get(&point).unwrap_or(&self.default))
+    }
+
+    fn get_mut(&mut self, point: IPoint) -> Option<&mut T> {
+        if !self.in_bounds(point) {
+            return None;
+        }
+        Some(
+            self.elems
+                .entry(point)
+                .or_insert_with(|| self.default.clone()),
+        )
+    }
+
+    fn offset(&self, (x, y): IPoint, (dx, dy): IPoint) -> Option<IPoint> {
+        let moved = (x + dx, y + dy);
+        self.in_bounds(moved).then_some(moved)
+    }
+}
+
+grid_impls!(Sparse, IPoint, Clone);
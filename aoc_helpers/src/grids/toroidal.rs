@@ -0,0 +1,71 @@
+use super::{grid_impls, Dense, Grid};
+use crate::{IPoint, UPoint};
+
+/// A grid that has wrapping (toroidal) indexing
+///
+/// Indexed by [`IPoint`]; every point is valid, and is wrapped back into the underlying grid.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Toroidal<T>(pub Dense<T>);
+
+/// Modify unconstrained `index` to index into something of size `len`
+#[must_use]
+pub fn toroidal_index_single(index: isize, len: usize) -> usize {
+    index.rem_euclid(len as isize) as usize
+}
+
+impl<T> Toroidal<T> {
+    /// Wrap a grid so that it tiles infinitely
+    #[must_use]
+    pub fn new(inner: Dense<T>) -> Self {
+        Self(inner)
+    }
+
+    #[must_use]
+    fn convert_index(&self, (x, y): IPoint) -> UPoint {
+        let (xlim, ylim) = self.0.dims();
+        (
+            toroidal_index_single(x, xlim),
+            toroidal_index_single(y, ylim),
+        )
+    }
+}
+
+impl<T> Grid for Toroidal<T> {
+    type Point = IPoint;
+    type Elem = T;
+
+    fn dims(&self) -> UPoint {
+        self.0.dims()
+    }
+
+    fn point_at(&self, (x, y): UPoint) -> IPoint {
+        (x as isize, y as isize)
+    }
+
+    fn get(&self, point: IPoint) -> Option<&T> {
+        if self.0.elems().is_empty() {
+            return None;
+        }
+        self.0.get(self.convert_index(point))
+    }
+
+    fn get_mut(&mut self, point: IPoint) -> Option<&mut T> {
+        if self.0.elems().is_empty() {
+            return None;
+        }
+        let index = self.convert_index(point);
+        self.0.get_mut(index)
+    }
+
+    fn offset(&self, (x, y): IPoint, (dx, dy): IPoint) -> Option<IPoint> {
+        let (w, h) = self.dims();
+        (w != 0 && h != 0).then(|| {
+            (
+                toroidal_index_single(x + dx, w) as isize,
+                toroidal_index_single(y + dy, h) as isize,
+            )
+        })
+    }
+}
+
+grid_impls!(Toroidal, IPoint);
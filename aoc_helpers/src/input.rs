@@ -0,0 +1,314 @@
+//! Finding and reading puzzle inputs
+//!
+//! By default each year's inputs are kept in its own crate, at `aocYYYY/input/YYYY/dayN.txt`, apart
+//! from 2019's which are at `aoc2019/dayN/data.txt`.
+//! Setting `AOC_INPUT_DIR`, or `dir` under `[inputs]` in `aoc.toml` at the root of the repository,
+//! moves them all to `DIR/YYYY/dayN.txt` instead.
+//!
+//! Inputs for other accounts can be kept as named profiles, in a subdirectory named after the
+//! profile next to where the default input would be, e.g. `aoc2022/input/2022/work/day7.txt`.
+//! The profile is picked with `AOC_INPUT_PROFILE`, or `profile` under `[inputs]`:
+//!
+//! ```toml
+//! [inputs]
+//! dir = "inputs"
+//! profile = "work"
+//! ```
+
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the config file, at the root of the repository
+pub const CONFIG_FILE: &str = "aoc.toml";
+/// Environment variable overriding the input directory
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Environment variable overriding the input profile
+pub const PROFILE_VAR: &str = "AOC_INPUT_PROFILE";
+
+/// Problems finding or reading an input
+#[derive(Debug)]
+pub enum InputError {
+    /// There's no input for the day
+    Missing {
+        /// The puzzle's year
+        year: u32,
+        /// The puzzle's day
+        day: u32,
+        /// Where the input should be
+        path: PathBuf,
+    },
+    /// The input exists but couldn't be read
+    Read(PathBuf, io::Error),
+    /// The config file couldn't be read or isn't valid
+    Config(PathBuf, String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { year, day, path } => {
+                write!(f, "no input for {year} day {day} at {}", path.display())
+            }
+            Self::Read(path, err) => write!(f, "can't read {}: {err}", path.display()),
+            Self::Config(path, reason) => write!(f, "{}: {reason}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The root of the repository, which holds the year crates
+pub(crate) fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc_helpers is inside the repository")
+        .to_path_buf()
+}
+
+/// Where the config file is, whether or not it exists
+#[must_use]
+pub fn config_path() -> PathBuf {
+    repo_root().join(CONFIG_FILE)
+}
+
+/// Convert line endings to `\n` and remove trailing whitespace, leaving leading whitespace alone
+#[must_use]
+pub fn normalise(text: &str) -> String {
+    text.replace("\r\n", "\n").trim_end().to_string()
+}
+
+/// Where to find inputs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inputs {
+    dir: Option<PathBuf>,
+    profile: Option<String>,
+}
+
+impl Inputs {
+    /// Read the config file if there is one, then apply any environment variables on top
+    ///
+    /// # Errors
+    /// If the config file exists but can't be read or parsed
+    pub fn from_env() -> Result<Inputs, InputError> {
+        let root = repo_root();
+        let config_path = config_path();
+        let mut inputs = match fs::read_to_string(&config_path) {
+            Ok(text) => {
+                Self::from_config(&text, &root).map_err(|e| InputError::Config(config_path, e))?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Inputs::default(),
+            Err(err) => return Err(InputError::Config(config_path, err.to_string())),
+        };
+
+        if let Some(dir) = env::var_os(DIR_VAR).filter(|d| !d.is_empty()) {
+            inputs = inputs.with_dir(dir);
+        }
+        if let Ok(profile) = env::var(PROFILE_VAR) {
+            inputs = inputs.with_profile(profile);
+        }
+        Ok(inputs)
+    }
+
+    /// Parse the `[inputs]` table of a config file, with relative directories taken from `root`
+    ///
+    /// # Errors
+    /// If the text isn't valid TOML, or `dir` or `profile` aren't strings
+    pub fn from_config(text: &str, root: &Path) -> Result<Inputs, String> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let Some(section) = table.get("inputs") else {
+            return Ok(Inputs::default());
+        };
+        let string = |key: &str| match section.get(key) {
+            None => Ok(None),
+            Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+            Some(other) => Err(format!(
+                "inputs.{key} should be a string, found {}",
+                other.type_str()
+            )),
+        };
+
+        let mut inputs = Inputs::default();
+        if let Some(dir) = string("dir")? {
+            inputs = inputs.with_dir(root.join(dir));
+        }
+        if let Some(profile) = string("profile")? {
+            inputs = inputs.with_profile(profile);
+        }
+        Ok(inputs)
+    }
+
+    /// Keep every year's inputs under `dir`, as `dir/YYYY/dayN.txt`
+    #[must_use]
+    pub fn with_dir(self, dir: impl Into<PathBuf>) -> Inputs {
+        Inputs {
+            dir: Some(dir.into()),
+            ..self
+        }
+    }
+
+    /// Use a named profile's inputs, where an empty name or `default` means my own
+    #[must_use]
+    pub fn with_profile(self, profile: impl Into<String>) -> Inputs {
+        let profile = profile.into();
+        Inputs {
+            profile: (!profile.is_empty() && profile != "default").then_some(profile),
+            ..self
+        }
+    }
+
+    /// The named profile in use, if it's not the default
+    #[must_use]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Where the input for a day is, or would be
+    #[must_use]
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        let (mut path, file) = match &self.dir {
+            Some(dir) => (dir.join(year.to_string()), format!("day{day}.txt")),
+            None if year == 2019 => (
+                repo_root().join("aoc2019").join(format!("day{day}")),
+                "data.txt".to_string(),
+            ),
+            None => (
+                repo_root()
+                    .join(format!("aoc{year}"))
+                    .join("input")
+                    .join(year.to_string()),
+                format!("day{day}.txt"),
+            ),
+        };
+        if let Some(profile) = &self.profile {
+            path.push(profile);
+        }
+        path.join(file)
+    }
+
+    /// Read the input for a day, normalised with [`normalise`]
+    ///
+    /// # Errors
+    /// If there's no input for the day, or it can't be read
+    pub fn load(&self, year: u32, day: u32) -> Result<String, InputError> {
+        let path = self.path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(normalise(&text)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(InputError::Missing { year, day, path })
+            }
+            Err(err) => Err(InputError::Read(path, err)),
+        }
+    }
+}
+
+/// Read an input from a particular file, normalised with [`normalise`]
+///
+/// # Errors
+/// If the file can't be read
+pub fn read(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    fs::read_to_string(path)
+        .map(|text| normalise(&text))
+        .map_err(|err| InputError::Read(path.to_path_buf(), err))
+}
+
+/// Read the input for a day from wherever the environment says inputs are
+///
+/// # Errors
+/// If the config is invalid, or the input is missing or can't be read
+pub fn load(year: u32, day: u32) -> Result<String, InputError> {
+    Inputs::from_env()?.load(year, day)
+}
+
+/// Run a test which needs the input for a day, skipping it if there's no input
+///
+/// This is for tests of an input other than checking the answer, which
+/// [`crate::answers::assert_answer`] does. A skipped test is noted in its output.
+///
+/// # Panics
+/// If the config is invalid or the input can't be read, or `test` panics
+pub fn with_input(year: u32, day: u32, test: impl FnOnce(&str)) {
+    match load(year, day) {
+        Ok(input) => test(&input),
+        Err(err @ InputError::Missing { .. }) => println!("{year} day {day}: skipped, {err}"),
+        Err(err) => panic!("{err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalising() {
+        assert_eq!(normalise("1\r\n2\r\n\r\n"), "1\n2");
+        assert_eq!(normalise("    [D]\n[N] [C]\n\n"), "    [D]\n[N] [C]");
+    }
+
+    #[test]
+    fn paths() {
+        let root = repo_root();
+        assert_eq!(
+            Inputs::default().path(2022, 7),
+            root.join("aoc2022/input/2022/day7.txt")
+        );
+        assert_eq!(
+            Inputs::default().path(2019, 5),
+            root.join("aoc2019/day5/data.txt")
+        );
+        assert_eq!(
+            Inputs::default().with_profile("work").path(2022, 7),
+            root.join("aoc2022/input/2022/work/day7.txt")
+        );
+        assert_eq!(
+            Inputs::default().with_dir("/tmp/aoc").path(2022, 7),
+            PathBuf::from("/tmp/aoc/2022/day7.txt")
+        );
+        assert_eq!(
+            Inputs::default()
+                .with_profile("work")
+                .with_profile("default")
+                .profile(),
+            None
+        );
+    }
+
+    #[test]
+    fn config() {
+        let root = Path::new("/repo");
+        assert_eq!(
+            Inputs::from_config("[inputs]\ndir = \"inputs\"\nprofile = \"work\"", root).unwrap(),
+            Inputs::default()
+                .with_dir("/repo/inputs")
+                .with_profile("work")
+        );
+        assert_eq!(Inputs::from_config("", root).unwrap(), Inputs::default());
+        assert!(Inputs::from_config("[inputs]\ndir = 3", root).is_err());
+    }
+
+    #[test]
+    fn loading() {
+        let text = Inputs::default().load(2022, 7).unwrap();
+        assert!(text.starts_with("$ cd /"));
+        assert!(!text.ends_with('\n'));
+        assert!(matches!(
+            Inputs::default().load(2022, 16),
+            Err(InputError::Missing {
+                year: 2022,
+                day: 16,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn skipping() {
+        let mut ran = false;
+        with_input(2022, 7, |input| ran = input.starts_with("$ cd /"));
+        assert!(ran);
+        with_input(2022, 16, |_| panic!("there's no input for 2022 day 16"));
+    }
+}
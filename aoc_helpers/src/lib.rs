@@ -16,10 +16,17 @@ pub type UPoint = Pair<usize>;
 /// Signed 2D point
 pub type IPoint = Pair<isize>;
 
+pub mod answer;
+pub mod answers;
 pub mod arith;
+pub mod cycles;
 pub mod grids;
+pub mod input;
+pub mod linear;
 pub mod manhattan;
+pub mod numtheory;
 pub mod ranges;
+pub mod search;
 
 /// Parsing helpers
 pub mod parse {
@@ -32,12 +39,12 @@ pub mod parse {
     pub type IResult<'a, T> = nom::IResult<&'a str, T>;
 
     /// Parses a [`u64`] into a [`usize`]
-    pub fn usize(input: &str) -> IResult<usize> {
+    pub fn usize(input: &str) -> IResult<'_, usize> {
         map(u64, |x| x.try_into().unwrap())(input)
     }
 
     /// Parses a [`i64`] into a [`isize`]
-    pub fn isize(input: &str) -> IResult<isize> {
+    pub fn isize(input: &str) -> IResult<'_, isize> {
         map(i64, |x| x.try_into().unwrap())(input)
     }
 }
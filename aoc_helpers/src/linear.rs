@@ -0,0 +1,387 @@
+//! Small systems of linear equations with integer coefficients
+//!
+//! Systems are given as a matrix of coefficients, one row per equation, and a right-hand side with
+//! one entry per equation, so `matrix * x == rhs`.
+//! Coefficients are `i64`, but all the work is done with `i128`s to leave room for the products
+//! that elimination builds up.
+
+use crate::numtheory::{self, gcd, gcd_all};
+
+/// A solution to a linear system, as integer numerators over a common denominator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fractions {
+    /// Numerator of each unknown
+    pub numerators: Vec<i128>,
+    /// Denominator shared by every unknown, always positive and as small as possible
+    pub denominator: i128,
+}
+
+impl Fractions {
+    fn new(mut numerators: Vec<i128>, mut denominator: i128) -> Self {
+        if denominator < 0 {
+            denominator = -denominator;
+            for n in &mut numerators {
+                *n = -*n;
+            }
+        }
+        let common = gcd(gcd_all(numerators.iter().copied()), denominator);
+        if common > 1 {
+            for n in &mut numerators {
+                *n /= common;
+            }
+            denominator /= common;
+        }
+        Self {
+            numerators,
+            denominator,
+        }
+    }
+
+    /// Get the solution as integers, if every unknown is one
+    #[must_use]
+    pub fn integers(&self) -> Option<Vec<i128>> {
+        (self.denominator == 1).then(|| self.numerators.clone())
+    }
+}
+
+/// Every possible outcome of solving a linear system
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solution {
+    /// The equations contradict each other
+    None,
+    /// There is exactly one solution
+    Unique(Fractions),
+    /// There are infinitely many solutions
+    Infinite,
+}
+
+/// Solve a system of two equations in two unknowns with Cramer's rule
+///
+/// # Example
+///
+/// ```
+/// # use aoc_helpers::linear::{solve_2x2, Solution};
+/// // 2x + y = 5, x - y = 1
+/// let Solution::Unique(s) = solve_2x2([[2, 1], [1, -1]], [5, 1]) else {
+///     panic!()
+/// };
+/// assert_eq!(s.integers(), Some(vec![2, 1]));
+/// ```
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> Solution {
+    let [[a, b], [c, d]] = matrix.map(|row| row.map(i128::from));
+    let [e, f] = rhs.map(i128::from);
+
+    let det = a * d - b * c;
+    let det_x = e * d - b * f;
+    let det_y = a * f - e * c;
+
+    if det != 0 {
+        Solution::Unique(Fractions::new(vec![det_x, det_y], det))
+    } else if det_x != 0 || det_y != 0 || ([a, b, c, d] == [0; 4] && [e, f] != [0; 2]) {
+        Solution::None
+    } else {
+        Solution::Infinite
+    }
+}
+
+/// A system in reduced row echelon form
+struct Reduced {
+    /// `(pivot column, coefficients, rhs)` for each non-zero row
+    ///
+    /// The pivot coefficient is positive, and every other pivot column is zero.
+    rows: Vec<(usize, Vec<i128>, i128)>,
+    /// Columns without a pivot, whose unknowns can be chosen freely
+    free: Vec<usize>,
+}
+
+/// Gauss-Jordan elimination, using only integer row operations
+///
+/// Returns `None` if the system is inconsistent.
+fn reduce(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<Reduced> {
+    assert_eq!(matrix.len(), rhs.len(), "one rhs entry needed per equation");
+    let width = matrix.first().map_or(0, Vec::len);
+    assert!(
+        matrix.iter().all(|row| row.len() == width),
+        "every equation needs the same number of coefficients"
+    );
+
+    // Augmented matrix, with the rhs as the last column
+    let mut rows: Vec<Vec<i128>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &r)| row.iter().copied().chain([r]).map(i128::from).collect())
+        .collect();
+
+    let mut pivots = Vec::new();
+    let mut free = Vec::new();
+    for col in 0..width {
+        let next = pivots.len();
+        let Some(found) = (next..rows.len()).find(|&r| rows[r][col] != 0) else {
+            free.push(col);
+            continue;
+        };
+        rows.swap(next, found);
+
+        let pivot = rows[next].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == next || factor == 0 {
+                continue;
+            }
+            for (x, &p) in row.iter_mut().zip(&pivot) {
+                *x = *x * pivot[col] - p * factor;
+            }
+            // Keep the numbers small
+            let common = gcd_all(row.iter().copied());
+            if common > 1 {
+                for x in row.iter_mut() {
+                    *x /= common;
+                }
+            }
+        }
+        pivots.push(col);
+    }
+
+    // Any row left over is all zeroes, so its rhs had better be too
+    if rows[pivots.len()..].iter().any(|row| row[width] != 0) {
+        return None;
+    }
+
+    let rows = pivots
+        .into_iter()
+        .zip(rows)
+        .map(|(col, mut row)| {
+            if row[col] < 0 {
+                for x in &mut row {
+                    *x = -*x;
+                }
+            }
+            let r = row.pop().unwrap_or_default();
+            (col, row, r)
+        })
+        .collect();
+
+    Some(Reduced { rows, free })
+}
+
+/// Solve a system of any number of equations in any number of unknowns
+///
+/// Uses fraction-free Gaussian elimination, so the result is exact.
+///
+/// # Panics
+///
+/// If `rhs` doesn't have an entry for every equation, or the equations have different numbers of
+/// coefficients.
+#[must_use]
+pub fn solve(matrix: &[Vec<i64>], rhs: &[i64]) -> Solution {
+    let Some(reduced) = reduce(matrix, rhs) else {
+        return Solution::None;
+    };
+    if !reduced.free.is_empty() {
+        return Solution::Infinite;
+    }
+
+    // Scale every row up to a common pivot
+    let denominator = reduced
+        .rows
+        .iter()
+        .try_fold(1, |acc, (col, row, _)| numtheory::lcm(acc, row[*col]))
+        .expect("solution denominator overflowed");
+    let numerators = reduced
+        .rows
+        .iter()
+        .map(|(col, row, r)| r * (denominator / row[*col]))
+        .collect();
+
+    Solution::Unique(Fractions::new(numerators, denominator))
+}
+
+/// Find the non-negative integer solution to a system which minimises `cost · x`
+///
+/// Returns the solution along with its cost, or `None` if there are no non-negative integer
+/// solutions or the cost has no minimum.
+///
+/// When the system has infinitely many solutions, they may have at most one degree of freedom.
+///
+/// # Panics
+///
+/// As for [`solve`], or if `cost` doesn't have an entry for every unknown, or the solutions have
+/// more than one degree of freedom.
+///
+/// # Example
+///
+/// ```
+/// # use aoc_helpers::linear::minimise;
+/// // x + 2y = 10: (10, 0), (8, 1), ..., (0, 5)
+/// assert_eq!(minimise(&[vec![1, 2]], &[10], &[1, 1]), Some((vec![0, 5], 5)));
+/// assert_eq!(minimise(&[vec![1, 2]], &[10], &[1, 3]), Some((vec![10, 0], 10)));
+/// ```
+#[must_use]
+pub fn minimise(matrix: &[Vec<i64>], rhs: &[i64], cost: &[i64]) -> Option<(Vec<i128>, i128)> {
+    let reduced = reduce(matrix, rhs)?;
+    let width = reduced.rows.len() + reduced.free.len();
+    assert_eq!(cost.len(), width, "one cost needed per unknown");
+
+    let total = |x: &[i128]| x.iter().zip(cost).map(|(&x, &c)| x * i128::from(c)).sum();
+
+    // Work out every unknown from the free one, if all the pivot unknowns come out as
+    // non-negative integers
+    let from_free = |free: Option<(usize, i128)>| -> Option<Vec<i128>> {
+        let mut x = vec![0; width];
+        if let Some((col, value)) = free {
+            x[col] = value;
+        }
+        for (col, row, r) in &reduced.rows {
+            let rest = free.map_or(0, |(f, value)| row[f] * value);
+            let (value, rem) = ((r - rest) / row[*col], (r - rest) % row[*col]);
+            if rem != 0 || value < 0 {
+                return None;
+            }
+            x[*col] = value;
+        }
+        Some(x)
+    };
+
+    let free = match reduced.free[..] {
+        [] => {
+            return from_free(None).map(|x| {
+                let t = total(&x);
+                (x, t)
+            })
+        }
+        [free] => free,
+        _ => panic!("can only minimise over one degree of freedom"),
+    };
+
+    // Each pivot row reads `d * x_pivot + c * x_free == r`.
+    // The pivot unknown is an integer when `c * x_free ≡ r (mod d)`, which pins the free unknown
+    // to a residue class, and is non-negative when `c * x_free <= r`, which bounds it.
+    let mut congruences = Vec::new();
+    let (mut lo, mut hi) = (0, None);
+    for (col, row, r) in &reduced.rows {
+        let (d, c) = (row[*col], row[free]);
+        let g = gcd(c, d);
+        if r % g != 0 {
+            return None;
+        }
+        let modulus = d / g;
+        let inverse = numtheory::mod_inverse(to_i64(c / g % modulus), to_i64(modulus))?;
+        congruences.push((
+            to_i64((r / g % modulus) * i128::from(inverse) % modulus),
+            to_i64(modulus),
+        ));
+
+        match c.signum() {
+            1 => hi = Some(hi.map_or(r.div_euclid(c), |h: i128| h.min(r.div_euclid(c)))),
+            -1 => lo = lo.max(-r.div_euclid(-c)),
+            _ if *r < 0 => return None,
+            _ => {}
+        }
+    }
+    let (residue, step) = numtheory::crt(congruences)?;
+    let (residue, step) = (i128::from(residue), i128::from(step));
+
+    // The cost changes linearly with the free unknown, so the best solution is at one end
+    let first = lo + (residue - lo).rem_euclid(step);
+    let last = match hi {
+        Some(hi) if hi < first => return None,
+        Some(hi) => hi - (hi - residue).rem_euclid(step),
+        // Unbounded above, so if the cost goes down there's no minimum
+        None => first + step,
+    };
+
+    let first = from_free(Some((free, first)))?;
+    let last = from_free(Some((free, last)))?;
+    let (first_cost, last_cost) = (total(&first), total(&last));
+    if hi.is_none() && last_cost < first_cost {
+        None
+    } else if last_cost < first_cost {
+        Some((last, last_cost))
+    } else {
+        Some((first, first_cost))
+    }
+}
+
+fn to_i64(x: i128) -> i64 {
+    i64::try_from(x).expect("linear system too large to reason about modulo i64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cramer() {
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Solution::Unique(Fractions {
+                numerators: vec![80, 40],
+                denominator: 1
+            })
+        );
+        assert_eq!(
+            solve_2x2([[2, 0], [0, 4]], [1, 1]),
+            Solution::Unique(Fractions {
+                numerators: vec![2, 1],
+                denominator: 4
+            })
+        );
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), Solution::Infinite);
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 7]), Solution::None);
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 0]), Solution::Infinite);
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 1]), Solution::None);
+    }
+
+    #[test]
+    fn elimination() {
+        let matrix = [vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let Solution::Unique(s) = solve(&matrix, &[8, -11, -3]) else {
+            panic!("expected a unique solution");
+        };
+        assert_eq!(s.integers(), Some(vec![2, 3, -1]));
+
+        // Agrees with Cramer
+        for (m, r) in [
+            ([[94, 22], [34, 67]], [8400, 5400]),
+            ([[3, 5], [6, 10]], [4, 8]),
+            ([[3, 5], [6, 10]], [4, 9]),
+            ([[2, 0], [0, 4]], [1, 1]),
+        ] {
+            let rows: Vec<_> = m.iter().map(|row| row.to_vec()).collect();
+            assert_eq!(solve(&rows, &r), solve_2x2(m, r), "{m:?} {r:?}");
+        }
+
+        // Overdetermined
+        let matrix = [vec![1, 1], vec![1, -1], vec![2, 1]];
+        assert!(matches!(solve(&matrix, &[3, 1, 5]), Solution::Unique(_)));
+        assert_eq!(solve(&matrix, &[3, 1, 6]), Solution::None);
+    }
+
+    #[test]
+    fn minimising() {
+        // Unique
+        assert_eq!(
+            minimise(&[vec![94, 22], vec![34, 67]], &[8400, 5400], &[3, 1]),
+            Some((vec![80, 40], 280))
+        );
+        assert_eq!(minimise(&[vec![1, 1], vec![1, -1]], &[1, 3], &[1, 1]), None);
+
+        // Degenerate: 3a + 5b = 40 and the same again doubled
+        let matrix = [vec![3, 5], vec![6, 10]];
+        assert_eq!(minimise(&matrix, &[40, 80], &[3, 1]), Some((vec![0, 8], 8)));
+        assert_eq!(
+            minimise(&matrix, &[40, 80], &[1, 3]),
+            Some((vec![10, 2], 16))
+        );
+        assert_eq!(minimise(&matrix, &[7, 14], &[1, 1]), None);
+
+        // Unbounded: a - b = 2
+        assert_eq!(
+            minimise(&[vec![1, -1]], &[2], &[1, 1]),
+            Some((vec![2, 0], 2))
+        );
+        assert_eq!(minimise(&[vec![1, -1]], &[2], &[-1, 0]), None);
+    }
+}
@@ -0,0 +1,237 @@
+//! Number theory: gcd and lcm, modular arithmetic and the Chinese remainder theorem
+//!
+//! The modular operations work on 64-bit integers, using 128-bit intermediates so that products
+//! of residues never overflow.
+
+use num_traits::{PrimInt, Signed};
+
+/// Greatest common divisor of `a` and `b`
+///
+/// The result is never negative, and `gcd(0, 0)` is `0`.
+///
+/// # Panics
+///
+/// Only if the result doesn't fit in `T`, which happens for signed types when it would be
+/// `-T::min_value()`, as for `gcd(T::min_value(), 0)`.
+#[must_use]
+pub fn gcd<T: PrimInt>(mut a: T, mut b: T) -> T {
+    while b != T::zero() {
+        // `T::min_value() % -1` overflows, though the answer is just 1
+        if b == T::one() || (b < T::zero() && b + T::one() == T::zero()) {
+            return T::one();
+        }
+        (a, b) = (b, a % b);
+    }
+    if a < T::zero() {
+        T::zero().checked_sub(&a).expect("gcd overflowed")
+    } else {
+        a
+    }
+}
+
+/// Lowest common multiple of `a` and `b`
+///
+/// Returns `None` if the result overflows, and `0` if either input is `0`.
+#[must_use]
+pub fn lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    let l = (a / gcd(a, b)).checked_mul(&b)?;
+    if l < T::zero() {
+        T::zero().checked_sub(&l)
+    } else {
+        Some(l)
+    }
+}
+
+/// Greatest common divisor of every number in `values`, or `0` if there are none
+///
+/// # Panics
+///
+/// As for [`gcd`].
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// Lowest common multiple of every number in `values`, or `1` if there are none
+///
+/// Returns `None` if the result overflows.
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::one(), |acc, x| lcm(acc, x))
+}
+
+/// Extended Euclidean algorithm
+///
+/// Returns `(g, x, y)` such that `g` is the gcd of `a` and `b`, and `a * x + b * y == g`.
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn ext_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while r != T::zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Multiplicative inverse of `a` modulo `m`, in `0..m`
+///
+/// Returns `None` if `a` and `m` aren't coprime, so no inverse exists.
+///
+/// # Panics
+///
+/// If `m` isn't positive.
+#[must_use]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = ext_gcd(i128::from(a), i128::from(m));
+    // Reduced modulo an `i64`, so it fits back into one
+    #[allow(clippy::cast_possible_truncation)]
+    (g == 1).then(|| x.rem_euclid(i128::from(m)) as i64)
+}
+
+/// Calculate `base` to the power of `exp`, modulo `modulus`
+///
+/// # Panics
+///
+/// If `modulus` is `0`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus must be non-zero");
+    let m = u128::from(modulus);
+    let mut base = u128::from(base) % m;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Solve a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem
+///
+/// The moduli don't need to be coprime.
+/// Returns `(x, m)` where `m` is the lcm of every modulus and `x` is the unique solution in
+/// `0..m`, so every solution is of the form `x + k * m`.
+/// An empty system gives `(0, 1)`.
+///
+/// Returns `None` if the congruences contradict each other, or the lcm of the moduli doesn't fit
+/// in an `i64`.
+///
+/// # Panics
+///
+/// If any modulus isn't positive.
+///
+/// # Example
+///
+/// ```
+/// # use aoc_helpers::numtheory::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// // Non-coprime moduli
+/// assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+/// assert_eq!(crt([(0, 4), (1, 6)]), None);
+/// ```
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (x, m) =
+        congruences
+            .into_iter()
+            .try_fold((0i128, 1i128), |(x, m), (residue, modulus)| {
+                assert!(modulus > 0, "modulus must be positive");
+                let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+
+                // Find k such that x + m * k ≡ residue (mod modulus)
+                let (g, inv, _) = ext_gcd(m, modulus);
+                let diff = residue - x;
+                if diff % g != 0 {
+                    return None;
+                }
+                let step = modulus / g;
+                let k = (diff / g % step * inv).rem_euclid(step);
+
+                let l = m * step;
+                if l > i128::from(i64::MAX) {
+                    return None;
+                }
+                Some(((x + m * k).rem_euclid(l), l))
+            })?;
+
+    // Both already checked to be no more than `i64::MAX`
+    #[allow(clippy::cast_possible_truncation)]
+    Some((x as i64, m as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd(i32::MIN, -1), 1);
+        assert_eq!(gcd(-1, i32::MIN), 1);
+        assert_eq!(gcd(i32::MIN, 2), 2);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(200u8, 3), None);
+
+        assert_eq!(gcd_all([24, 36, 60]), 12);
+        assert_eq!(gcd_all::<u32>([]), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all::<u32>([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, 2]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd overflowed")]
+    fn gcd_overflow() {
+        let _ = gcd(i32::MIN, 0);
+    }
+
+    #[test]
+    fn extended() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 0), (0, 5)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "({a}, {b})");
+            assert_eq!(a * x + b * y, g, "({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 7)]), Some((6, 7)));
+        assert_eq!(crt([(2, 6), (8, 10), (8, 15)]), Some((8, 30)));
+        assert_eq!(crt([(1, 6), (2, 10)]), None);
+        assert_eq!(crt([(0, i64::MAX), (0, i64::MAX - 1)]), None);
+    }
+}
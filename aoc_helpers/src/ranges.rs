@@ -1,13 +1,14 @@
 //! Utilities for dealing with ranges and their combinations
 
-/// An inclusive, contiguous range of `usize`
-// TODO: make generic over the element type
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Range {
+use num_traits::PrimInt;
+
+/// An inclusive, contiguous range of integers
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Range<T = usize> {
     /// Start of the range, inclusive
-    pub start: usize,
+    pub start: T,
     /// End of the range, inclusive
-    pub end: usize,
+    pub end: T,
 }
 
 /// Relationship between one range and another
@@ -26,33 +27,71 @@ pub enum RangeRel {
 }
 
 /// Enumeration of possible `diff` interactions
-pub enum DiffResult {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DiffResult<T = usize> {
     /// rhs completely contains lhs thus the result is the empty set
     Empty,
     /// lhs does not change (no overlap)
-    NoChange(Range),
+    NoChange(Range<T>),
     /// successful diff
-    Success(Range),
+    Success(Range<T>),
     /// successful diff, lhs is bisected
-    SuccessBisect(Range, Range),
+    SuccessBisect(Range<T>, Range<T>),
+}
+
+impl<T> DiffResult<T> {
+    /// Iterate over the ranges left over by the diff
+    pub fn into_ranges(self) -> impl Iterator<Item = Range<T>> {
+        use DiffResult::*;
+
+        let (l, r) = match self {
+            Empty => (None, None),
+            NoChange(l) | Success(l) => (Some(l), None),
+            SuccessBisect(l, r) => (Some(l), Some(r)),
+        };
+        l.into_iter().chain(r)
+    }
 }
 
-impl Range {
+impl<T: PrimInt> Range<T> {
+    /// Create a new range, sorting the bounds if necessary
+    #[must_use]
+    pub fn new(a: T, b: T) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// Create a range from a start point and a (non-zero) number of elements
+    ///
+    /// # Panics
+    ///
+    /// If `len` is zero.
+    #[must_use]
+    pub fn from_len(start: T, len: T) -> Self {
+        assert!(len > T::zero(), "ranges cannot be empty");
+        Self {
+            start,
+            end: start + (len - T::one()),
+        }
+    }
+
     /// The number of elements in the range
     ///
     /// Guaranteed to be always at least `1`.
     #[must_use]
     #[allow(clippy::len_without_is_empty)]
-    pub fn len(self) -> usize {
-        self.end - self.start + 1
+    pub fn len(self) -> T {
+        self.end - self.start + T::one()
     }
 
     /// Tries to extract one number from `self`
     ///
     /// If `self` covers more than one number, returns `None`.
     #[must_use]
-    pub fn to_single(self) -> Option<usize> {
-        if self.len() == 1 {
+    pub fn to_single(self) -> Option<T> {
+        if self.start == self.end {
             Some(self.start)
         } else {
             None
@@ -61,7 +100,7 @@ impl Range {
 
     /// Find the [`RangeRel`] of `self` to `other`
     #[must_use]
-    pub fn relationship(self, other: Range) -> RangeRel {
+    pub fn relationship(self, other: Self) -> RangeRel {
         if self.is_superset(other) {
             RangeRel::Contains
         } else if other.is_superset(self) {
@@ -77,21 +116,28 @@ impl Range {
 
     /// Check is `self` is a superset of `rhs`
     #[must_use]
-    pub fn is_superset(self, rhs: Range) -> bool {
+    pub fn is_superset(self, rhs: Self) -> bool {
         self.start <= rhs.start && self.end >= rhs.end
     }
 
     /// Check if `self` contains a particular value
     #[must_use]
-    pub fn contains(self, x: usize) -> bool {
-        (self.start..=self.end).contains(&x)
+    pub fn contains(self, x: T) -> bool {
+        self.start <= x && x <= self.end
+    }
+
+    /// Check if `self` and `rhs` overlap or sit directly next to each other
+    #[must_use]
+    pub fn touches(self, rhs: Self) -> bool {
+        self.start <= rhs.end.saturating_add(T::one())
+            && rhs.start <= self.end.saturating_add(T::one())
     }
 
     /// Try to find the union of `self` and `rhs`
     ///
     /// Returns `None` if `self` and `rhs` are completely disjoint.
     #[must_use]
-    pub fn union(self, rhs: Range) -> Option<Self> {
+    pub fn union(self, rhs: Self) -> Option<Self> {
         match self.relationship(rhs) {
             RangeRel::Contains => Some(self),
             RangeRel::ContainedBy => Some(rhs),
@@ -107,11 +153,21 @@ impl Range {
         }
     }
 
+    /// Find the overlap of `self` and `rhs`
+    ///
+    /// Returns `None` if `self` and `rhs` are completely disjoint.
+    #[must_use]
+    pub fn intersection(self, rhs: Self) -> Option<Self> {
+        let start = self.start.max(rhs.start);
+        let end = self.end.min(rhs.end);
+        (start <= end).then_some(Self { start, end })
+    }
+
     /// Tries to "subtract" the right range pair from the left
     ///
     /// That is, it finds the result of `lhs / rhs` in set logic.
     #[must_use]
-    pub fn diff(self, rhs @ Range { start: rs, end: re }: Range) -> DiffResult {
+    pub fn diff(self, rhs @ Range { start: rs, end: re }: Self) -> DiffResult<T> {
         use DiffResult::*;
 
         let Range { start: ls, end: le } = self;
@@ -122,28 +178,28 @@ impl Range {
             RangeRel::ContainedBy => Empty,
             RangeRel::NoIntersect => NoChange(self),
             RangeRel::IntersectBeginning => Success(Self {
-                start: re + 1,
+                start: re + T::one(),
                 end: le,
             }),
             RangeRel::IntersectEnd => Success(Self {
                 start: ls,
-                end: rs - 1,
+                end: rs - T::one(),
             }),
             RangeRel::Contains if ls == rs => Success(Self {
-                start: re + 1,
+                start: re + T::one(),
                 end: le,
             }),
             RangeRel::Contains if le == re => Success(Self {
                 start: ls,
-                end: rs - 1,
+                end: rs - T::one(),
             }),
             RangeRel::Contains => SuccessBisect(
                 Self {
                     start: ls,
-                    end: rs - 1,
+                    end: rs - T::one(),
                 },
                 Self {
-                    start: re + 1,
+                    start: re + T::one(),
                     end: le,
                 },
             ),
@@ -152,35 +208,291 @@ impl Range {
 
     /// Repeatedly [`Range::diff`] the elements of `diffs` from `self`
     ///
-    /// # Note
-    ///
-    /// Assumes that only one range will remain.
-    #[must_use]
-    pub fn demolish(self, mut diffs: impl Iterator<Item = Self> + Clone) -> Option<Self> {
-        let mut current = self;
-        while let Some(rhs) = diffs.next() {
-            use DiffResult::*;
-            match current.diff(rhs) {
-                Empty => return None,
-                NoChange(_) => {}
-                Success(new) => current = new,
-                SuccessBisect(l, r) => {
-                    let l = l.demolish(diffs.clone());
-                    let r = r.demolish(diffs.clone());
-                    return l.or(r);
-                }
+    /// Returns everything left over, which may be any number of disjoint ranges.
+    #[must_use]
+    pub fn demolish(self, diffs: impl IntoIterator<Item = Self>) -> RangeSet<T> {
+        let mut remaining = RangeSet::from(self);
+        for rhs in diffs {
+            remaining.remove(rhs);
+            if remaining.is_empty() {
+                break;
+            }
+        }
+        remaining
+    }
+}
+
+/// Iterator over the values in a [`Range`]
+#[derive(Clone, Debug)]
+pub struct RangeIter<T> {
+    next: Option<T>,
+    end: T,
+}
+
+impl<T: PrimInt> Iterator for RangeIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next?;
+        self.next = if cur == self.end {
+            None
+        } else {
+            Some(cur + T::one())
+        };
+        Some(cur)
+    }
+}
+
+impl<T: PrimInt> IntoIterator for Range<T> {
+    type Item = T;
+    type IntoIter = RangeIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RangeIter {
+            next: Some(self.start),
+            end: self.end,
+        }
+    }
+}
+
+/// A set of integers, stored as a normalised list of [`Range`]s
+///
+/// The ranges are kept sorted, and no two of them overlap or sit directly next to each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T = usize> {
+    spans: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    /// Create a new, empty set
+    #[must_use]
+    pub fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    /// Check if the set contains no elements at all
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The total number of elements covered by the set
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.spans
+            .iter()
+            .fold(T::zero(), |acc, span| acc + span.len())
+    }
+
+    /// The number of disjoint ranges which make up the set
+    #[must_use]
+    pub fn span_count(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Iterate over the disjoint ranges in the set, in ascending order
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.spans.iter().copied()
+    }
+
+    /// The smallest element of the set
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.spans.first().map(|r| r.start)
+    }
+
+    /// The largest element of the set
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.spans.last().map(|r| r.end)
+    }
+
+    /// Check if the set contains a particular value
+    #[must_use]
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.spans.partition_point(|r| r.end < x);
+        self.spans.get(i).is_some_and(|r| r.contains(x))
+    }
+
+    /// Add every element of `range` to the set
+    pub fn insert(&mut self, range: Range<T>) {
+        let first = self
+            .spans
+            .partition_point(|r| !r.touches(range) && r.end < range.start);
+        let last = first + self.spans[first..].partition_point(|r| r.touches(range));
+
+        let merged = self.spans[first..last].iter().fold(range, |acc, r| Range {
+            start: acc.start.min(r.start),
+            end: acc.end.max(r.end),
+        });
+        self.spans.splice(first..last, [merged]);
+    }
+
+    /// Remove every element of `range` from the set
+    pub fn remove(&mut self, range: Range<T>) {
+        let first = self.spans.partition_point(|r| r.end < range.start);
+        let last = first + self.spans[first..].partition_point(|r| r.start <= range.end);
+
+        let leftover: Vec<_> = self.spans[first..last]
+            .iter()
+            .flat_map(|r| r.diff(range).into_ranges())
+            .collect();
+        self.spans.splice(first..last, leftover);
+    }
+
+    /// Find the elements present in both `self` and `rhs`
+    #[must_use]
+    pub fn intersection(&self, rhs: &Self) -> Self {
+        let mut spans = Vec::new();
+        let (mut li, mut ri) = (0, 0);
+
+        while let (Some(&l), Some(&r)) = (self.spans.get(li), rhs.spans.get(ri)) {
+            spans.extend(l.intersection(r));
+            if l.end < r.end {
+                li += 1;
+            } else {
+                ri += 1;
             }
         }
 
-        Some(current)
+        Self { spans }
+    }
+
+    /// Find the elements present in either `self` or `rhs`
+    #[must_use]
+    pub fn union(&self, rhs: &Self) -> Self {
+        let mut out = self.clone();
+        out.extend(rhs.iter());
+        out
+    }
+
+    /// Find the elements present in `self` but not in `rhs`
+    #[must_use]
+    pub fn difference(&self, rhs: &Self) -> Self {
+        let mut out = self.clone();
+        for range in rhs.iter() {
+            out.remove(range);
+        }
+        out
+    }
+
+    /// Find the elements of `bounds` which are *not* in the set
+    #[must_use]
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        bounds.demolish(self.iter())
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self { spans: vec![range] }
+    }
+}
+
+impl<T: PrimInt> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
     }
 }
 
-impl IntoIterator for Range {
-    type Item = usize;
-    type IntoIter = std::ops::RangeInclusive<usize>;
+impl<'a, T: PrimInt> IntoIterator for &'a RangeSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, Range<T>>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.start..=self.end
+        self.spans.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(spans: &[(i32, i32)]) -> RangeSet<i32> {
+        spans.iter().map(|&(s, e)| Range::new(s, e)).collect()
+    }
+
+    fn spans(set: &RangeSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        assert_eq!(
+            spans(&set(&[(5, 7), (0, 1), (10, 12), (2, 3)])),
+            vec![(0, 3), (5, 7), (10, 12)]
+        );
+        assert_eq!(spans(&set(&[(5, 7), (10, 12), (6, 11)])), vec![(5, 12)]);
+        assert_eq!(spans(&set(&[(-4, -2), (4, 5), (-10, 10)])), vec![(-10, 10)]);
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut s = set(&[(0, 10), (20, 30)]);
+        s.remove(Range::new(5, 22));
+        assert_eq!(spans(&s), vec![(0, 4), (23, 30)]);
+        s.remove(Range::new(2, 2));
+        assert_eq!(spans(&s), vec![(0, 1), (3, 4), (23, 30)]);
+        s.remove(Range::new(-100, 100));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn len_and_contains() {
+        let s = set(&[(0, 3), (10, 10)]);
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.span_count(), 2);
+        assert!(s.contains(10));
+        assert!(!s.contains(5));
+        assert_eq!((s.min(), s.max()), (Some(0), Some(10)));
+    }
+
+    #[test]
+    fn intersection() {
+        let l = set(&[(0, 5), (10, 15), (20, 25)]);
+        let r = set(&[(3, 12), (14, 22)]);
+        assert_eq!(
+            spans(&l.intersection(&r)),
+            vec![(3, 5), (10, 12), (14, 15), (20, 22)]
+        );
+    }
+
+    #[test]
+    fn complement() {
+        let s = set(&[(2, 3), (6, 8)]);
+        assert_eq!(
+            spans(&s.complement(Range::new(0, 10))),
+            vec![(0, 1), (4, 5), (9, 10)]
+        );
+        assert_eq!(spans(&s.complement(Range::new(4, 5))), vec![(4, 5)]);
+    }
+
+    #[test]
+    fn demolish_keeps_all_survivors() {
+        let left = Range::new(0u8, 20).demolish([Range::new(5, 6), Range::new(10, 11)]);
+        assert_eq!(
+            left.iter().collect::<Vec<_>>(),
+            vec![Range::new(0, 4), Range::new(7, 9), Range::new(12, 20)]
+        );
+    }
+
+    #[test]
+    fn iterate_values() {
+        assert_eq!(
+            Range::new(3u8, 5).into_iter().collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(Range::new(254u8, 255).into_iter().count(), 2);
     }
 }
@@ -0,0 +1,366 @@
+//! Graph searches: BFS, Dijkstra and A*
+//!
+//! Every search takes any number of starting nodes and a successor function, so they work as
+//! well on implicit graphs as they do on [grids](crate::grids).
+//! For grids, [`grid_steps`] builds a successor function from an [`Adjacency`] system.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use num_traits::Zero;
+
+use crate::grids::{Adjacency, Grid};
+use crate::{manhattan, Pair};
+
+/// A path found by a search
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Every node on the path, including the start and end
+    pub nodes: Vec<N>,
+    /// Total cost of the path
+    pub cost: C,
+}
+
+/// Nodes reached so far by a search, with the route taken to reach them
+struct Visited<N, C> {
+    index: HashMap<N, usize>,
+    /// `(node, parent index, cost)`
+    nodes: Vec<(N, Option<usize>, C)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Visited<N, C> {
+    fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Record `node` as reachable at `cost`, returning its index
+    fn insert(&mut self, node: N, parent: Option<usize>, cost: C) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            self.nodes[i] = (node, parent, cost);
+            i
+        } else {
+            let i = self.nodes.len();
+            self.index.insert(node.clone(), i);
+            self.nodes.push((node, parent, cost));
+            i
+        }
+    }
+
+    fn cost(&self, node: &N) -> Option<C> {
+        self.index.get(node).map(|&i| self.nodes[i].2)
+    }
+
+    fn path_to(&self, mut i: usize) -> Path<N, C> {
+        let cost = self.nodes[i].2;
+        let mut nodes = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            nodes.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        nodes.reverse();
+        Path { nodes, cost }
+    }
+
+    fn into_costs(self) -> HashMap<N, C> {
+        self.nodes.into_iter().map(|(n, _, c)| (n, c)).collect()
+    }
+}
+
+/// Breadth-first search for the shortest unweighted path to a node satisfying `success`
+///
+/// The cost of the returned path is the number of steps taken.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if visited.cost(&start).is_none() {
+            queue.push_back(visited.insert(start, None, 0));
+        }
+    }
+
+    while let Some(i) = queue.pop_front() {
+        let (node, _, cost) = visited.nodes[i].clone();
+        if success(&node) {
+            return Some(visited.path_to(i));
+        }
+        for next in successors(&node) {
+            if visited.cost(&next).is_none() {
+                queue.push_back(visited.insert(next, Some(i), cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Find the number of steps to every node reachable from `starts`
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut dists = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !dists.contains_key(&start) {
+            dists.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, dist)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !dists.contains_key(&next) {
+                dists.insert(next.clone(), dist + 1);
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    dists
+}
+
+/// Core of Dijkstra and A*
+///
+/// With `success` as `None`, runs until every reachable node has been visited.
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: Option<&mut dyn FnMut(&N) -> bool>,
+) -> (Visited<N, C>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if visited.cost(&start).is_none() {
+            let estimate = heuristic(&start);
+            heap.push(Reverse((
+                estimate,
+                C::zero(),
+                visited.insert(start, None, C::zero()),
+            )));
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // We've since found a better way here
+        if cost > visited.nodes[i].2 {
+            continue;
+        }
+
+        let node = visited.nodes[i].0.clone();
+        if success.as_mut().is_some_and(|f| f(&node)) {
+            return (visited, Some(i));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if visited.cost(&next).is_none_or(|c| next_cost < c) {
+                let estimate = next_cost + heuristic(&next);
+                heap.push(Reverse((
+                    estimate,
+                    next_cost,
+                    visited.insert(next, Some(i), next_cost),
+                )));
+            }
+        }
+    }
+
+    (visited, None)
+}
+
+/// Dijkstra's algorithm, finding the cheapest path to a node satisfying `success`
+///
+/// `successors` gives each neighbour of a node along with the cost of moving to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (visited, end) = best_first(starts, successors, |_| C::zero(), Some(&mut success));
+    end.map(|i| visited.path_to(i))
+}
+
+/// Find the cheapest cost to every node reachable from `starts`
+pub fn dijkstra_distances<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::zero(), None)
+        .0
+        .into_costs()
+}
+
+/// A* search, finding the cheapest path to a node satisfying `success`
+///
+/// `heuristic` must never overestimate the remaining cost to a successful node, otherwise the
+/// path found may not be the cheapest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (visited, end) = best_first(starts, successors, heuristic, Some(&mut success));
+    end.map(|i| visited.path_to(i))
+}
+
+/// Heuristic for [`astar`] on grids with orthogonal steps of cost at least `1`
+pub fn manhattan_to<T>(goal: Pair<T>) -> impl Fn(&Pair<T>) -> T
+where
+    T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>,
+{
+    move |&point| manhattan::distu(point, goal)
+}
+
+/// Build a successor function which steps between `A`-adjacent points on `grid`
+///
+/// Only steps for which `can_step(from, to)` holds are taken.
+pub fn grid_steps<'a, A, G>(
+    grid: &'a G,
+    mut can_step: impl FnMut(&G::Elem, &G::Elem) -> bool + 'a,
+) -> impl FnMut(&G::Point) -> Vec<G::Point> + 'a
+where
+    A: Adjacency,
+    G: Grid,
+{
+    move |&point| {
+        let Some(here) = grid.get(point) else {
+            return Vec::new();
+        };
+        grid.neighbours::<A>(point)
+            .filter(|(_, there)| can_step(here, there))
+            .map(|(p, _)| p)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grids::{self, Dense, Orthogonal};
+
+    const MAZE: &str = "S..#....
+.#.#.##.
+.#...#E.
+.####.#.
+........";
+
+    fn maze() -> (Dense<bool>, Pair<usize>, Pair<usize>) {
+        let parsed = grids::parse(MAZE, b"SE", |b| Some(b != b'#')).unwrap();
+        let start = parsed.single(b'S').unwrap();
+        let end = parsed.single(b'E').unwrap();
+        (parsed.grid, start, end)
+    }
+
+    #[test]
+    fn bfs_maze() {
+        let (grid, start, end) = maze();
+        let path = bfs(
+            [start],
+            grid_steps::<Orthogonal, _>(&grid, |_, &to| to),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 14);
+        assert_eq!(path.nodes.len(), 15);
+        assert_eq!((path.nodes[0], path.nodes[14]), (start, end));
+    }
+
+    /// Steps along the bottom row are cheaper than anywhere else
+    fn weighted_steps(
+        grid: &Dense<bool>,
+    ) -> impl FnMut(&Pair<usize>) -> Vec<(Pair<usize>, usize)> + '_ {
+        let mut steps = grid_steps::<Orthogonal, _>(grid, |_, &to| to);
+        move |p| {
+            steps(p)
+                .into_iter()
+                .map(|q| (q, if q.1 == 4 { 1 } else { 3 }))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn weighted_agree() {
+        let (grid, start, end) = maze();
+        let d = dijkstra([start], weighted_steps(&grid), |&p| p == end).unwrap();
+        let a = astar([start], weighted_steps(&grid), manhattan_to(end), |&p| {
+            p == end
+        })
+        .unwrap();
+
+        assert_eq!(d.cost, a.cost);
+        // Going the long way round along the bottom row is cheaper
+        assert_eq!(d.cost, 26);
+    }
+
+    #[test]
+    fn distances() {
+        let (grid, start, end) = maze();
+        let dists = bfs_distances(
+            [start, end],
+            grid_steps::<Orthogonal, _>(&grid, |_, &to| to),
+        );
+        assert_eq!(
+            dists.len(),
+            grid.elems().iter().filter(|&&open| open).count()
+        );
+        assert_eq!(dists[&(2, 2)], 4);
+        assert_eq!(dists[&(5, 4)], 5);
+
+        let weighted = dijkstra_distances([0u32], |&n| {
+            [(n + 1, 1u32), (n * 2, 1)]
+                .into_iter()
+                .filter(|&(m, _)| m <= 20)
+        });
+        assert_eq!(weighted[&20], 6);
+    }
+
+    #[test]
+    fn unreachable() {
+        assert_eq!(
+            bfs([0u8], |&n| [n.saturating_add(1)], |&n| n == 0).map(|p| p.cost),
+            Some(0)
+        );
+        assert_eq!(bfs([0u8], |_| [], |&n| n == 1), None);
+    }
+}